@@ -0,0 +1,52 @@
+//! Generates the module list of the runner binary (`src/bin/runner.rs`) from the solutions in `src/bin`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let file_name = entry.ok()?.file_name().into_string().ok()?;
+            let name = file_name.strip_suffix(".rs")?;
+            is_solution(name).then(|| name.to_string())
+        })
+        .collect();
+
+    names.sort();
+
+    let mut out = String::new();
+
+    for name in &names {
+        let path = bin_dir.join(format!("{name}.rs"));
+        out.push_str(&format!(
+            "#[path = {:?}]\nmod solution_{name};\n",
+            path.display().to_string()
+        ));
+    }
+
+    out.push_str("\n/// All solutions in `src/bin`, ordered by year and day.\n");
+    out.push_str("static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for name in &names {
+        out.push_str(&format!("    solution_{name}::SOLUTION,\n"));
+    }
+    out.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+}
+
+/// Solutions are named `<year>_<day>`, e.g. `2024_01`.
+fn is_solution(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    bytes.len() == 7
+        && bytes[4] == b'_'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| i == 4 || b.is_ascii_digit())
+}
@@ -0,0 +1,58 @@
+use advent_of_code::parse::{lines, parse_or_report, unsigned, IResult};
+use itertools::Itertools;
+use nom::{character::complete::space1, sequence::separated_pair};
+
+advent_of_code::solution!(2024, 1, parse);
+
+fn left_right_coordinates(input: &str) -> IResult<'_, (u32, u32)> {
+    separated_pair(unsigned, space1, unsigned)(input)
+}
+
+type Lists = (Vec<u32>, Vec<u32>);
+
+pub fn parse(input: &str) -> Lists {
+    parse_or_report(input, lines(left_right_coordinates))
+        .into_iter()
+        .unzip()
+}
+
+pub fn part_one((left_coords, right_coords): &Lists) -> Option<u32> {
+    Some(
+        left_coords
+            .iter()
+            .sorted()
+            .zip(right_coords.iter().sorted())
+            .map(|(&left_coord, &right_coord)| left_coord.abs_diff(right_coord))
+            .sum(),
+    )
+}
+
+pub fn part_two((left, right): &Lists) -> Option<u32> {
+    let right_counts = right.iter().counts();
+    Some(
+        left.iter()
+            .map(|num| (*right_counts.get(num).unwrap_or(&0) as u32) * num)
+            .sum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+        ));
+        assert_eq!(result, Some(11));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+        ));
+        assert_eq!(result, Some(31));
+    }
+}
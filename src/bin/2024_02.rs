@@ -1,21 +1,10 @@
+use advent_of_code::parse::{lines, parse_or_report, signed, whitespace_separated};
 use itertools::Itertools;
-use nom::{
-    character::complete::{i32, space1},
-    multi::separated_list1,
-    IResult,
-};
 
-advent_of_code::solution!(2);
-
-fn parse_report(input: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(space1, i32)(input)
-}
+advent_of_code::solution!(2024, 2);
 
 fn parse_input(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| parse_report(line).unwrap().1)
-        .collect()
+    parse_or_report(input, lines(whitespace_separated(signed)))
 }
 
 fn is_invalid_report(report: &[i32]) -> bool {
@@ -70,13 +59,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(4));
     }
 }
@@ -1,14 +1,15 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(2024, 3);
 
+use advent_of_code::parse::{unsigned, IResult};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{anychar, char, u32},
+    character::complete::{anychar, char},
     combinator::value,
     multi::{many1, many_till},
     sequence::{delimited, separated_pair},
-    IResult, Parser,
+    Parser,
 };
+use nom_supreme::tag::complete::tag;
 
 #[derive(Debug, Clone)]
 enum Op {
@@ -17,14 +18,17 @@ enum Op {
     Dont,
 }
 
-fn mul(input: &str) -> IResult<&str, Op> {
-    let (input, pair) =
-        delimited(tag("mul("), separated_pair(u32, char(','), u32), char(')'))(input)?;
+fn mul(input: &str) -> IResult<'_, Op> {
+    let (input, pair) = delimited(
+        tag("mul("),
+        separated_pair(unsigned::<u32>, char(','), unsigned),
+        char(')'),
+    )(input)?;
 
     Ok((input, Op::Mul(pair)))
 }
 
-fn op(input: &str) -> IResult<&str, Op> {
+fn op(input: &str) -> IResult<'_, Op> {
     let (input, op) = alt((
         mul,
         value(Op::Do, tag("do()")),
@@ -34,7 +38,7 @@ fn op(input: &str) -> IResult<&str, Op> {
     Ok((input, op))
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Op>> {
+fn parse_input(input: &str) -> IResult<'_, Vec<Op>> {
     many1(many_till(anychar, op).map(|(_discarded, op)| op))(input)
 }
 
@@ -73,15 +77,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(161));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 2).unwrap());
         assert_eq!(result, Some(48));
     }
 }
@@ -1,19 +1,12 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(2024, 4);
 
+use advent_of_code::{direction::Direction, grid::Grid};
 use itertools::Itertools;
-use pathfinding::matrix::{
-    directions::{self},
-    Matrix,
-};
-
-fn parse_input(input: &str) -> Matrix<char> {
-    Matrix::from_rows(input.lines().map(|l| l.chars())).unwrap()
-}
 
 fn is_word_in_direction(
-    letters: &Matrix<char>,
+    letters: &Grid<char>,
     coordinate: &(usize, usize),
-    direction: &(isize, isize),
+    direction: &Direction,
     word: &str,
 ) -> bool {
     letters
@@ -21,7 +14,7 @@ fn is_word_in_direction(
         .is_some_and(|&c| word.starts_with(c))
         && (word.len() == 1
             || letters
-                .move_in_direction(*coordinate, *direction)
+                .step(*coordinate, *direction)
                 .is_some_and(|next_coord| {
                     is_word_in_direction(letters, &next_coord, direction, &word[1..])
                 }))
@@ -29,16 +22,13 @@ fn is_word_in_direction(
 
 pub fn part_one(input: &str) -> Option<u32> {
     let word = "XMAS";
-    let letters = parse_input(input);
+    let letters = Grid::from_chars(input);
 
-    let starting_coords: Vec<_> = letters
-        .keys()
-        .filter(|&(row, column)| letters.get((row, column)) == Some(&'X'))
-        .collect();
+    let starting_coords: Vec<_> = letters.find_all(&'X').collect();
 
     let matches: Vec<_> = starting_coords
         .iter()
-        .cartesian_product(directions::DIRECTIONS_8)
+        .cartesian_product(Direction::ALL_8)
         .filter(|(coord, dir)| is_word_in_direction(&letters, coord, dir, word))
         .collect();
 
@@ -47,23 +37,15 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let word = "MAS";
-    let letters = parse_input(input);
+    let letters = Grid::from_chars(input);
 
-    let starting_coords: Vec<_> = letters
-        .keys()
-        .filter(|&(row, column)| letters.get((row, column)) == Some(&'M'))
-        .collect();
+    let starting_coords: Vec<_> = letters.find_all(&'M').collect();
 
     let matches: Vec<_> = starting_coords
         .iter()
-        .cartesian_product([
-            directions::NE,
-            directions::SE,
-            directions::SW,
-            directions::NW,
-        ])
+        .cartesian_product([Direction::NE, Direction::SE, Direction::SW, Direction::NW])
         .filter(|&(coord, dir)| is_word_in_direction(&letters, coord, &dir, word))
-        .map(|(&coord, dir)| letters.move_in_direction(coord, dir))
+        .map(|(&coord, dir)| letters.step(coord, dir))
         .duplicates()
         .collect();
 
@@ -76,15 +58,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 2).unwrap());
         assert_eq!(result, Some(9));
     }
 }
@@ -1,41 +1,31 @@
 use std::collections::HashMap;
 
+use advent_of_code::parse::{
+    blank_line, comma_separated, lines, parse_or_report, unsigned, IResult,
+};
 use gxhash::HashSet;
 use itertools::Itertools;
-use nom::{
-    character::complete::{char, newline, u32},
-    combinator::map,
-    multi::{many1, separated_list1},
-    sequence::{separated_pair, terminated},
-    IResult,
-};
+use nom::{character::complete::char, combinator::map, sequence::separated_pair};
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2024, 5);
 
 type Rules = HashMap<u32, HashSet<u32>>;
 type Updates = Vec<Vec<u32>>;
 
-fn rule(input: &str) -> IResult<&str, (u32, u32)> {
-    map(
-        terminated(separated_pair(u32, char('|'), u32), newline),
-        |(a, b)| (b, a),
-    )(input)
-}
-
-fn rules(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
-    terminated(many1(rule), newline)(input)
-}
-
-fn updates(input: &str) -> IResult<&str, Updates> {
-    many1(terminated(separated_list1(char(','), u32), newline))(input)
+fn rule(input: &str) -> IResult<'_, (u32, u32)> {
+    map(separated_pair(unsigned, char('|'), unsigned), |(a, b)| {
+        (b, a)
+    })(input)
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Rules, Updates)> {
-    let (input, rules) = rules(input)?;
-    let (input, updates) = updates(input)?;
+fn parse_input(input: &str) -> (Rules, Updates) {
+    let (rules, updates) = parse_or_report(
+        input,
+        separated_pair(lines(rule), blank_line, lines(comma_separated(unsigned))),
+    );
 
     let rules = rules.into_iter().into_grouping_map().collect();
-    Ok((input, (rules, updates)))
+    (rules, updates)
 }
 
 fn is_valid_update(update: &[u32], rules: &Rules) -> bool {
@@ -46,7 +36,7 @@ fn is_valid_update(update: &[u32], rules: &Rules) -> bool {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_input, (rules, updates)) = parse_input(input).unwrap();
+    let (rules, updates) = parse_input(input);
 
     Some(
         updates
@@ -58,7 +48,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_input, (rules, updates)) = parse_input(input).unwrap();
+    let (rules, updates) = parse_input(input);
 
     Some(
         updates
@@ -84,13 +74,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(123));
     }
 }
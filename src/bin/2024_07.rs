@@ -1,4 +1,6 @@
-advent_of_code::solution!(7);
+use advent_of_code::parse::{key_value, lines, parse_or_report, unsigned, whitespace_separated};
+
+advent_of_code::solution!(2024, 7);
 
 fn is_solvable(test_value: u64, rev_nums: &[u64], use_concat: bool) -> bool {
     let &num = rev_nums.first().unwrap();
@@ -26,21 +28,17 @@ fn is_solvable(test_value: u64, rev_nums: &[u64], use_concat: bool) -> bool {
     false
 }
 
-fn parse_equation(line: &str) -> (u64, Vec<u64>) {
-    let parts: Vec<&str> = line.split(": ").collect();
-    let test_value: u64 = parts[0].parse().unwrap();
-    let nums: Vec<u64> = parts[1]
-        .split_whitespace()
-        .map(|x| x.parse().unwrap())
-        .collect();
-    (test_value, nums)
+fn parse_input(input: &str) -> Vec<(u64, Vec<u64>)> {
+    parse_or_report(
+        input,
+        lines(key_value(unsigned, whitespace_separated(unsigned))),
+    )
 }
 
 fn solve(input: &str, use_concat: bool) -> Option<u64> {
     Some(
-        input
-            .lines()
-            .map(parse_equation)
+        parse_input(input)
+            .into_iter()
             .map(|(test_value, nums)| (test_value, nums.into_iter().rev().collect::<Vec<_>>()))
             .filter(|(test_value, nums)| is_solvable(*test_value, nums, use_concat))
             .map(|(test_value, _)| test_value)
@@ -62,13 +60,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(11387));
     }
 }
@@ -1,36 +1,19 @@
-use std::fmt::Display;
-
+use advent_of_code::grid::{Grid, Position};
 use itertools::Itertools;
 use num::integer::gcd;
-use pathfinding::matrix::Matrix;
-
-advent_of_code::solution!(8);
-
-fn parse_input(input: &str) -> Matrix<char> {
-    Matrix::from_rows(input.lines().map(|line| line.chars())).unwrap()
-}
 
-fn _print_grid<T: Display>(grid: &Matrix<T>) {
-    for r in grid.iter() {
-        for x in r {
-            print!("{x}");
-        }
-        println!();
-    }
-    println!();
-}
+advent_of_code::solution!(2024, 8);
 
-fn get_antinodes<F>(antennas: &Matrix<char>, antinode_calculator: F) -> Vec<(usize, usize)>
+fn get_antinodes<F>(antennas: &Grid<char>, antinode_calculator: F) -> Vec<(usize, usize)>
 where
-    F: Fn(Position, Position, &Matrix<char>) -> Vec<Position>,
+    F: Fn(Position, Position, &Grid<char>) -> Vec<Position>,
 {
     let antinodes: Vec<_> = antennas
         .items()
         .filter(|&(_, &freq)| freq != '.')
         .into_group_map_by(|(_, &freq)| freq)
-        .into_iter()
-        .flat_map(|(_freq, antennas_group)| {
-            // println!("For freq={_freq}:");
+        .into_values()
+        .flat_map(|antennas_group| {
             antennas_group
                 .into_iter()
                 .tuple_combinations()
@@ -41,12 +24,10 @@ where
     antinodes
 }
 
-type Position = (usize, usize);
-
 fn calculate_antinodes(
     (ax, ay): Position,
     (bx, by): Position,
-    antennas: &Matrix<char>,
+    antennas: &Grid<char>,
 ) -> Vec<(usize, usize)> {
     let mut antinodes = Vec::new();
 
@@ -66,7 +47,7 @@ fn calculate_antinodes(
 fn calculate_antinodes_2(
     (ax, ay): Position,
     (bx, by): Position,
-    antennas: &Matrix<char>,
+    antennas: &Grid<char>,
 ) -> Vec<(usize, usize)> {
     let (ax, ay) = (ax as i32, ay as i32);
     let (bx, by) = (bx as i32, by as i32);
@@ -92,13 +73,13 @@ fn calculate_antinodes_2(
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let antennas = parse_input(input);
+    let antennas = Grid::from_chars(input);
     let antinodes = get_antinodes(&antennas, calculate_antinodes);
 
     Some(antinodes.into_iter().unique().count())
 }
 pub fn part_two(input: &str) -> Option<usize> {
-    let antennas = parse_input(input);
+    let antennas = Grid::from_chars(input);
     let antinodes = get_antinodes(&antennas, calculate_antinodes_2);
     Some(antinodes.into_iter().unique().count())
 }
@@ -109,13 +90,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(34));
     }
 }
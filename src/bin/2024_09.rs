@@ -1,6 +1,6 @@
 use core::fmt;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2024, 9);
 
 #[derive(Clone)]
 enum Block {
@@ -44,7 +44,7 @@ fn dense_disk_map_to_sparse(disk_map: &str) -> Vec<Block> {
                 true => Block::Used(ix / 2),
                 false => Block::Free,
             };
-            std::iter::repeat(block_type).take(size)
+            std::iter::repeat_n(block_type, size)
         })
         .collect()
 }
@@ -154,13 +154,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(1928));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(2858));
     }
 }
@@ -1,24 +1,17 @@
-use pathfinding::{
-    matrix::Matrix,
-    prelude::{count_paths, dfs_reach},
-};
+use advent_of_code::grid::{Grid, Position};
+use pathfinding::prelude::{count_paths, dfs_reach};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2024, 10);
 
-fn parse_input(input: &str) -> Matrix<u32> {
-    Matrix::from_rows(
-        input
-            .lines()
-            .map(|l| l.chars().map(|c| c.to_digit(10).unwrap())),
-    )
-    .unwrap()
+fn parse_input(input: &str) -> Grid<u32> {
+    Grid::parse(input, |c| c.to_digit(10).unwrap())
 }
 
 fn adjacent_higher_positions<'map>(
-    map: &'map Matrix<u32>,
-    coord: (usize, usize),
+    map: &'map Grid<u32>,
+    coord: Position,
     val: &'map u32,
-) -> Vec<((usize, usize), &'map u32)> {
+) -> Vec<(Position, &'map u32)> {
     map.neighbours(coord, false)
         .map(|c| (c, map.get(c).unwrap()))
         .filter(|&(_next_coord, &next_val)| next_val == (val + 1))
@@ -64,13 +57,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(36));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(81));
     }
 }
@@ -1,6 +1,6 @@
 use cached::proc_macro::cached;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2024, 11);
 
 fn parse_input(input: &str) -> Vec<u64> {
     input
@@ -16,7 +16,7 @@ fn process_stone(stone: u64, blinks: u64) -> u64 {
     match (stone, blinks) {
         (_, 0) => 1,
         (0, _) => process_stone(1, blinks - 1),
-        (_s, _) if (digits % 2) == 0 => {
+        (_s, _) if digits.is_multiple_of(2) => {
             let op = 10_u64.pow(digits / 2);
             process_stone(stone / op, blinks - 1) + process_stone(stone % op, blinks - 1)
         }
@@ -52,13 +52,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(65601038650482));
     }
 }
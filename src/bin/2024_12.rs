@@ -1,33 +1,32 @@
 use std::collections::BTreeSet;
 
-use itertools::Itertools;
-use pathfinding::{
-    grid::Grid,
-    matrix::{directions, Matrix},
-    utils::move_in_direction,
+use advent_of_code::{
+    direction::Direction,
+    grid::{self, Position},
 };
+use itertools::Itertools;
+use pathfinding::{grid::Grid, utils::move_in_direction};
 
-advent_of_code::solution!(12);
-
-fn parse_input(input: &str) -> Matrix<char> {
-    Matrix::from_rows(input.lines().map(|l| l.chars())).unwrap()
-}
+advent_of_code::solution!(2024, 12);
 
-fn count_sides(coord: &(usize, usize), plot: &Grid) -> usize {
-    const CORNER_ENDS: [[(isize, isize); 3]; 4] = [
-        [directions::E, directions::S, directions::SE],
-        [directions::W, directions::S, directions::SW],
-        [directions::S, directions::E, directions::SE],
-        [directions::N, directions::E, directions::NE],
+fn count_sides(coord: &Position, plot: &Grid) -> usize {
+    const CORNER_ENDS: [[Direction; 3]; 4] = [
+        [Direction::E, Direction::S, Direction::SE],
+        [Direction::W, Direction::S, Direction::SW],
+        [Direction::S, Direction::E, Direction::SE],
+        [Direction::N, Direction::E, Direction::NE],
     ];
 
     CORNER_ENDS
         .iter()
         .filter(|corner_end| {
             let dimensions = (plot.width, plot.height);
-            let edge = move_in_direction(*coord, corner_end[0], dimensions).unwrap_or_default();
-            let corner1 = move_in_direction(*coord, corner_end[1], dimensions).unwrap_or_default();
-            let corner2 = move_in_direction(*coord, corner_end[2], dimensions).unwrap_or_default();
+            let edge =
+                move_in_direction(*coord, corner_end[0].offset(), dimensions).unwrap_or_default();
+            let corner1 =
+                move_in_direction(*coord, corner_end[1].offset(), dimensions).unwrap_or_default();
+            let corner2 =
+                move_in_direction(*coord, corner_end[2].offset(), dimensions).unwrap_or_default();
 
             !plot.has_vertex(edge) && (!plot.has_vertex(corner1) || plot.has_vertex(corner2))
         })
@@ -35,7 +34,7 @@ fn count_sides(coord: &(usize, usize), plot: &Grid) -> usize {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let map = parse_input(input);
+    let map = grid::Grid::from_chars(input);
 
     let plants: BTreeSet<char> = map
         .values()
@@ -69,7 +68,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let map = parse_input(input);
+    let map = grid::Grid::from_chars(input);
 
     let plants: BTreeSet<char> = map
         .values()
@@ -111,13 +110,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(1930));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(1206));
     }
 }
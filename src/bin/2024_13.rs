@@ -1,14 +1,12 @@
+use advent_of_code::parse::{blocks, key_value, label, parse_or_report, signed, IResult};
 use nom::{
-    character::complete::{anychar, i64, line_ending},
-    combinator::{map, opt},
-    multi::{many1, many_till},
-    sequence::{preceded, separated_pair, terminated, tuple},
-    IResult,
+    character::complete::{anychar, line_ending},
+    combinator::map,
+    sequence::{preceded, separated_pair, tuple},
 };
+use nom_supreme::{tag::complete::tag, ParserExt};
 
-use nom_supreme::{error::ErrorTree, tag::complete::tag, ParserExt};
-
-advent_of_code::solution!(13);
+advent_of_code::solution!(2024, 13);
 
 #[derive(Debug)]
 struct Machine {
@@ -17,44 +15,36 @@ struct Machine {
     prize: (i64, i64),
 }
 
-fn label(input: &str) -> IResult<&str, String, ErrorTree<&str>> {
-    let (input, label) = map(many_till(anychar, tag(": ")), |(label, _)| label)(input)?;
-    Ok((input, String::from_iter(label)))
-}
-
-fn xy(input: &str) -> IResult<&str, (i64, i64), ErrorTree<&str>> {
+fn xy(input: &str) -> IResult<'_, (i64, i64)> {
     separated_pair(
         preceded(
             tuple((tag("X"), anychar)).context("Should be preceeded by X"),
-            i64,
+            signed,
         ),
         tag(", "),
         preceded(
             tuple((tag("Y"), anychar)).context("Should be preceeded by Y"),
-            i64,
+            signed,
         ),
     )(input)
 }
 
-fn line(input: &str) -> IResult<&str, (i64, i64), ErrorTree<&str>> {
-    let (input, (_, xy, _)) = tuple((
-        label,
-        xy,
-        line_ending.context("Should end with line_ending"),
-    ))(input)?;
-    Ok((input, xy))
+fn line(input: &str) -> IResult<'_, (i64, i64)> {
+    map(key_value(label, xy), |(_, xy)| xy)(input)
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Machine>, ErrorTree<&str>> {
+fn parse_input(input: &str) -> Vec<Machine> {
     let machine = map(
-        terminated(
-            tuple((line, line, line)).context("Each machine should have 3 lines"),
-            opt(line_ending),
-        ),
+        tuple((
+            line,
+            preceded(line_ending, line),
+            preceded(line_ending, line),
+        ))
+        .context("Each machine should have 3 lines"),
         |(a, b, prize)| Machine { a, b, prize },
     );
 
-    many1(machine)(input)
+    parse_or_report(input, blocks(machine))
 }
 
 const A_COST: i64 = 3;
@@ -99,12 +89,12 @@ fn solve(machines: &[Machine], prize_offset: i64) -> i64 {
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let (_, machines) = parse_input(input).unwrap();
+    let machines = parse_input(input);
     Some(solve(&machines, 0))
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let (_, machines) = parse_input(input).unwrap();
+    let machines = parse_input(input);
     Some(solve(&machines, PRIZE_OFFSET))
 }
 
@@ -114,13 +104,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(875318608908));
     }
 }
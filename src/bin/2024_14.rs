@@ -1,17 +1,19 @@
 use std::{thread::sleep, time};
 
+use advent_of_code::{
+    parse::{coordinates, lines, parse_or_report, IResult},
+    point::Point,
+};
 use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{char, i32, line_ending, space1},
+    character::complete::space1,
     combinator::map,
-    multi::many1,
-    sequence::{preceded, separated_pair, terminated},
-    IResult,
+    sequence::{preceded, separated_pair},
 };
+use nom_supreme::tag::complete::tag;
 use pathfinding::grid::Grid;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2024, 14);
 
 #[cfg(test)]
 mod constants {
@@ -29,33 +31,29 @@ mod constants {
 
 use constants::*;
 
-type XY = (i32, i32);
+const BOUNDS: Point<i32> = Point::new(WIDTH, HEIGHT);
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct Robot {
-    position: XY,
-    velocity: XY,
+    position: Point<i32>,
+    velocity: Point<i32>,
 }
 
-fn xy(input: &str) -> IResult<&str, XY> {
-    separated_pair(i32, char(','), i32)(input)
+fn xy(input: &str) -> IResult<'_, Point<i32>> {
+    map(coordinates, |(x, y)| Point::new(x, y))(input)
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Robot>> {
+fn parse_input(input: &str) -> Vec<Robot> {
     let robot = map(
-        terminated(
-            separated_pair(preceded(tag("p="), xy), space1, preceded(tag("v="), xy)),
-            line_ending,
-        ),
+        separated_pair(preceded(tag("p="), xy), space1, preceded(tag("v="), xy)),
         |(position, velocity)| Robot { position, velocity },
     );
-    let (input, robots) = many1(robot)(input)?;
 
-    Ok((input, robots))
+    parse_or_report(input, lines(robot))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_input, robots) = parse_input(input).unwrap();
+    let robots = parse_input(input);
 
     let steps = 100;
     let x_mid = (WIDTH - 1) / 2;
@@ -64,15 +62,10 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(
         robots
             .iter()
-            .map(move |r| {
-                (
-                    ((r.position.0 + r.velocity.0 * steps).rem_euclid(WIDTH)),
-                    ((r.position.1 + r.velocity.1 * steps).rem_euclid(HEIGHT)),
-                )
-            })
-            // .inspect(|&c| println!("Coord: {c:?}"))
-            .filter(|&(x, y)| x != x_mid && y != y_mid)
-            .map(|(x, y)| ((x < ((WIDTH - 1) / 2)), (y < ((HEIGHT - 1) / 2))))
+            .map(move |r| (r.position + r.velocity * steps).rem_euclid(&BOUNDS))
+            // .inspect(|&c| println!("Coord: {c}"))
+            .filter(|p| p.x != x_mid && p.y != y_mid)
+            .map(|p| ((p.x < x_mid), (p.y < y_mid)))
             // .inspect(|(x, y)| println!("Cuadrant: {x},{y}"))
             .sorted()
             .dedup_with_count()
@@ -82,17 +75,14 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<bool> {
-    let (_input, mut robots) = parse_input(input).unwrap();
+    let mut robots = parse_input(input);
     let steps = 1;
 
     let mut found = false;
     let mut t = 0;
     while !found && t < 100000 {
         robots.iter_mut().for_each(move |r| {
-            r.position = (
-                ((r.position.0 + r.velocity.0 * steps).rem_euclid(WIDTH)),
-                ((r.position.1 + r.velocity.1 * steps).rem_euclid(HEIGHT)),
-            );
+            r.position = (r.position + r.velocity * steps).rem_euclid(&BOUNDS);
         });
         t += 1;
 
@@ -104,7 +94,7 @@ pub fn part_two(input: &str) -> Option<bool> {
         let g = Grid::from_iter(
             robots
                 .iter()
-                .map(|r| (r.position.0 as usize, r.position.1 as usize)),
+                .map(|r| (r.position.x as usize, r.position.y as usize)),
         );
         println!("t={t}\n{g:#?}");
         sleep(time::Duration::from_millis(50));
@@ -119,13 +109,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(true));
     }
 }
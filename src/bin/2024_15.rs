@@ -1,8 +1,11 @@
 use std::fmt;
 
-use pathfinding::matrix::{directions, Matrix};
+use advent_of_code::{
+    direction::Direction,
+    grid::{Grid, Position},
+};
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2024, 15);
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum MapElement {
@@ -31,15 +34,12 @@ impl fmt::Display for MapElement {
     }
 }
 
-type Position = (usize, usize);
-type Move = (isize, isize);
-
-fn parse_input(input: &str, is_wide: bool) -> (Matrix<MapElement>, Vec<Move>) {
+fn parse_input(input: &str, is_wide: bool) -> (Grid<MapElement>, Vec<Direction>) {
     let (map_lines, move_lines): (Vec<&str>, Vec<&str>) =
         input.lines().partition(|l| l.starts_with('#'));
 
     let map = if !is_wide {
-        Matrix::from_rows(map_lines.iter().map(|l| {
+        Grid::from_rows(map_lines.iter().map(|l| {
             l.chars().map(|c| match c {
                 '#' => MapElement::Wall,
                 'O' => MapElement::Box,
@@ -49,7 +49,7 @@ fn parse_input(input: &str, is_wide: bool) -> (Matrix<MapElement>, Vec<Move>) {
             })
         }))
     } else {
-        Matrix::from_rows(map_lines.iter().map(|l| {
+        Grid::from_rows(map_lines.iter().map(|l| {
             l.chars().flat_map(|c| match c {
                 '#' => [MapElement::Wall, MapElement::Wall],
                 'O' => [MapElement::BoxLeft, MapElement::BoxRight],
@@ -58,20 +58,14 @@ fn parse_input(input: &str, is_wide: bool) -> (Matrix<MapElement>, Vec<Move>) {
                 _ => panic!(),
             })
         }))
-    }
-    .unwrap();
+    };
 
     let moves = move_lines
         .iter()
         .filter(|line| !line.is_empty())
         .flat_map(|line| {
-            line.chars().map(|move_| match move_ {
-                '^' => directions::N,
-                '>' => directions::E,
-                'v' => directions::S,
-                '<' => directions::W,
-                _ => panic!(),
-            })
+            line.chars()
+                .map(|move_| Direction::try_from(move_).unwrap())
         })
         .collect();
 
@@ -80,11 +74,11 @@ fn parse_input(input: &str, is_wide: bool) -> (Matrix<MapElement>, Vec<Move>) {
 
 fn try_move(
     position: Position,
-    move_: Move,
-    map: &mut Matrix<MapElement>,
+    move_: Direction,
+    map: &mut Grid<MapElement>,
     update: bool,
 ) -> Option<Position> {
-    map.move_in_direction(position, move_)
+    map.step(position, move_)
         .and_then(|next_pos| match map.get(next_pos) {
             Some(MapElement::Wall) => None,
             Some(MapElement::Box | MapElement::BoxLeft | MapElement::BoxRight) => {
@@ -107,21 +101,21 @@ fn try_move(
 
 fn try_move_wide(
     position: Position,
-    move_: Move,
-    map: &mut Matrix<MapElement>,
+    move_: Direction,
+    map: &mut Grid<MapElement>,
     update: bool,
 ) -> Option<Position> {
-    map.move_in_direction(position, move_)
+    map.step(position, move_)
         .and_then(|next_pos| match (map.get(next_pos), move_) {
-            (_, directions::E | directions::W) => try_move(position, move_, map, true),
+            (_, Direction::E | Direction::W) => try_move(position, move_, map, true),
             (Some(MapElement::Wall), _) => None,
             (Some(box_side @ (MapElement::BoxLeft | MapElement::BoxRight)), _) => {
                 let side_dir = match box_side {
-                    MapElement::BoxLeft => directions::E,
-                    MapElement::BoxRight => directions::W,
+                    MapElement::BoxLeft => Direction::E,
+                    MapElement::BoxRight => Direction::W,
                     _ => panic!(),
                 };
-                let next_pos_side = map.move_in_direction(next_pos, side_dir).unwrap();
+                let next_pos_side = map.step(next_pos, side_dir).unwrap();
                 let this = try_move_wide(next_pos, move_, map, false);
                 let side = try_move_wide(next_pos_side, move_, map, false);
 
@@ -141,47 +135,24 @@ fn try_move_wide(
                 }
                 Some(next_pos)
             }
-            _ => panic!("Elem={:?} dir={:?}", map.get(next_pos), move_),
+            _ => panic!("Elem={:?} dir={move_}", map.get(next_pos)),
         })
 }
 
-fn _print_grid(grid: &Matrix<MapElement>) {
-    for r in grid.iter() {
-        for x in r {
-            print!("{x}");
-        }
-        println!();
-    }
-    println!();
-}
-
-fn _move_repr(&move_: &Move) -> char {
-    match move_ {
-        directions::N => '^',
-        directions::E => '>',
-        directions::S => 'v',
-        directions::W => '<',
-        _ => panic!(),
-    }
-}
-
 pub fn part_one(input: &str) -> Option<u32> {
     let (mut map, moves) = parse_input(input, false);
 
-    let (mut robot_pos, _) = map
-        .items()
-        .find(|&(_, element)| element == &MapElement::Robot)
-        .unwrap();
+    let mut robot_pos = map.find(&MapElement::Robot).unwrap();
 
     // println!("Initial state:");
-    // _print_grid(&map);
+    // println!("{map}");
 
     moves.iter().for_each(|&move_| {
-        // println!("Move: {:?}:", _move_repr(&move_));
+        // println!("Move: {move_}:");
         if let Some(new_robot_pos) = try_move(robot_pos, move_, &mut map, true) {
             robot_pos = new_robot_pos;
         }
-        // _print_grid(&map);
+        // println!("{map}");
     });
 
     Some(
@@ -195,20 +166,17 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let (mut map, moves) = parse_input(input, true);
 
-    let (mut robot_pos, _) = map
-        .items()
-        .find(|&(_, element)| element == &MapElement::Robot)
-        .unwrap();
+    let mut robot_pos = map.find(&MapElement::Robot).unwrap();
 
     // println!("Initial state:");
-    // _print_grid(&map);
+    // println!("{map}");
 
     moves.iter().for_each(|&move_| {
-        // println!("Move: {:?}:", _move_repr(&move_));
+        // println!("Move: {move_}:");
         if let Some(new_robot_pos) = try_move_wide(robot_pos, move_, &mut map, true) {
             robot_pos = new_robot_pos;
         }
-        // _print_grid(&map);
+        // println!("{map}");
     });
 
     Some(
@@ -225,17 +193,16 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
+        let result =
+            part_one(&advent_of_code::template::read_file_part("examples", PUZZLE, 1).unwrap());
         assert_eq!(result, Some(2028));
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(10092));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(9021));
     }
 }
@@ -0,0 +1,124 @@
+use advent_of_code::{
+    direction::{Direction, Turn},
+    grid::{Grid, Position},
+    point::Point,
+};
+use itertools::Itertools;
+use pathfinding::prelude::{astar_bag, dijkstra};
+use std::fmt;
+
+advent_of_code::solution!(2024, 16);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum MapElement {
+    Start,
+    Free,
+    Wall,
+    End,
+}
+
+impl fmt::Display for MapElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MapElement::Start => "S",
+                MapElement::Wall => "#",
+                MapElement::Free => ".",
+                MapElement::End => "E",
+            }
+        )
+    }
+}
+
+fn parse_input(input: &str) -> (Grid<MapElement>, Position, Position) {
+    let map = Grid::parse(input, |c| match c {
+        'S' => MapElement::Start,
+        '.' => MapElement::Free,
+        '#' => MapElement::Wall,
+        'E' => MapElement::End,
+        _ => panic!(),
+    });
+    let start = map.find(&MapElement::Start).unwrap();
+    let end = map.find(&MapElement::End).unwrap();
+
+    (map, start, end)
+}
+
+fn neighbours(
+    position: Position,
+    direction: Direction,
+    map: &Grid<MapElement>,
+) -> Vec<((Position, Direction), u32)> {
+    let mut next_nodes = Vec::with_capacity(3);
+
+    for dir in [direction.turn(Turn::Left), direction.turn(Turn::Right)] {
+        let next_orthogonal = map.step(position, dir).unwrap();
+        if map.get(next_orthogonal).unwrap() != &MapElement::Wall {
+            next_nodes.push(((position, dir), 1000));
+        }
+    }
+
+    let next_straight = map.step(position, direction).unwrap();
+    if map.get(next_straight).unwrap() != &MapElement::Wall {
+        next_nodes.push(((next_straight, direction), 1));
+    }
+
+    next_nodes
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let (map, start, end) = parse_input(input);
+
+    Some(
+        dijkstra(
+            &(start, Direction::E),
+            |&(pos, dir)| neighbours(pos, dir, &map),
+            |(pos, _)| *pos == end,
+        )
+        .unwrap()
+        .1,
+    )
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let (map, start, end) = parse_input(input);
+
+    astar_bag(
+        &(start, Direction::E),
+        |&(pos, dir)| neighbours(pos, dir, &map),
+        |&(pos, _)| Point::from_position(pos).manhattan(Point::from_position(start)) as u32,
+        |(pos, _)| *pos == end,
+    )
+    .map(|(solutions, _)| {
+        solutions
+            .into_iter()
+            .flat_map(|sol| sol.into_iter().map(|(c, _)| c))
+            .unique()
+            .count()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
+        assert_eq!(result, Some(7036));
+        let result =
+            part_one(&advent_of_code::template::read_file_part("examples", PUZZLE, 1).unwrap());
+        assert_eq!(result, Some(11048));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
+        assert_eq!(result, Some(45));
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 1).unwrap());
+        assert_eq!(result, Some(64));
+    }
+}
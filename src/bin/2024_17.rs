@@ -0,0 +1,57 @@
+use advent_of_code::{
+    parse::{blank_line, comma_separated, key_value, label, lines, parse_or_report, unsigned},
+    vm::{self, Registers},
+};
+use itertools::Itertools;
+use nom::{
+    combinator::{map, map_res},
+    sequence::separated_pair,
+};
+
+advent_of_code::solution!(2024, 17);
+
+fn parse_input(input: &str) -> (Registers, Vec<u8>) {
+    let registers = map_res(
+        lines(key_value(label, unsigned)),
+        |values| match values[..] {
+            [(_, a), (_, b), (_, c)] => Ok(Registers { a, b, c }),
+            _ => Err("there should be 3 registers"),
+        },
+    );
+    let program = map(
+        key_value(label, comma_separated(unsigned)),
+        |(_, program)| program,
+    );
+
+    parse_or_report(input, separated_pair(registers, blank_line, program))
+}
+
+pub fn part_one(input: &str) -> Option<String> {
+    let (registers, program) = parse_input(input);
+
+    Some(vm::run(&program, registers).unwrap().into_iter().join(","))
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let (registers, program) = parse_input(input);
+
+    vm::find_quine(&program, registers).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
+        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 2).unwrap());
+        assert_eq!(result, Some(265061364597659));
+    }
+}
@@ -1,9 +1,9 @@
 use std::{collections::HashMap, fmt};
 
-use itertools::Itertools;
+use advent_of_code::parse::{coordinates, lines, parse_or_report};
 use pathfinding::{matrix::Matrix, prelude::dijkstra};
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(2024, 18);
 
 type Position = (usize, usize);
 
@@ -43,17 +43,10 @@ mod constants {
 use constants::*;
 
 fn parse_input(input: &str, (max_x, max_y): Position) -> (i32, Matrix<i32>) {
-    let coords: HashMap<Position, i32> = input
-        .lines()
+    let coords: HashMap<Position, i32> = parse_or_report(input, lines(coordinates))
+        .into_iter()
         .enumerate()
-        .map(|(t, line)| {
-            let (x, y) = line
-                .split(",")
-                .map(|c| c.parse::<usize>().unwrap())
-                .collect_tuple()
-                .unwrap();
-            ((x, y), t as i32)
-        })
+        .map(|(t, position)| (position, t as i32))
         .collect();
 
     (
@@ -108,13 +101,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(22));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(PositionResult(6, 1)));
     }
 }
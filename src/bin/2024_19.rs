@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use cached::proc_macro::cached;
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(2024, 19);
 
 type AvailableTowels = BTreeMap<char, Vec<String>>;
 
@@ -88,13 +88,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(16));
     }
 }
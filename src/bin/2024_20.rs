@@ -1,11 +1,13 @@
 use gxhash::{HashMap, HashSet, HashSetExt};
 
-use pathfinding::{
-    matrix::{directions::DIRECTIONS_4, Matrix},
-    prelude::dfs,
+use advent_of_code::{
+    direction::Direction,
+    grid::{Grid, Position},
+    point::Point,
 };
+use pathfinding::prelude::dfs;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(2024, 20);
 
 #[cfg(test)]
 mod constants {
@@ -25,16 +27,12 @@ mod constants {
 
 use constants::*;
 
-fn parse_input(input: &str) -> Matrix<char> {
-    Matrix::from_rows(input.lines().map(|l| l.chars())).unwrap()
+fn distance(c1: &Position, c2: &Position) -> usize {
+    Point::from_position(*c1).manhattan(Point::from_position(*c2))
 }
 
-fn distance(c1: &(usize, usize), c2: &(usize, usize)) -> usize {
-    c1.0.abs_diff(c2.0) + c1.1.abs_diff(c2.1)
-}
-
-fn count_possible_cheats(map: &Matrix<char>, max_hack_time: i32, min_saving: i32) -> usize {
-    let start = map.items().find(|(_, &v)| v == 'S').unwrap().0;
+fn count_possible_cheats(map: &Grid<char>, max_hack_time: i32, min_saving: i32) -> usize {
+    let start = map.find(&'S').unwrap();
     let racetrack = dfs(
         start,
         |&n| {
@@ -45,7 +43,7 @@ fn count_possible_cheats(map: &Matrix<char>, max_hack_time: i32, min_saving: i32
     )
     .unwrap();
 
-    let racetrack_positions: HashMap<&(usize, usize), i32> = racetrack
+    let racetrack_positions: HashMap<&Position, i32> = racetrack
         .iter()
         .enumerate()
         .map(|(pos, coord)| (coord, pos as i32))
@@ -75,9 +73,9 @@ fn count_possible_cheats(map: &Matrix<char>, max_hack_time: i32, min_saving: i32
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let map = parse_input(input);
-    let start = map.items().find(|(_, &v)| v == 'S').unwrap().0;
-    let racetrack: HashMap<(usize, usize), i32> = dfs(
+    let map = Grid::from_chars(input);
+    let start = map.find(&'S').unwrap();
+    let racetrack: HashMap<Position, i32> = dfs(
         start,
         |&n| {
             map.neighbours(n, false)
@@ -95,11 +93,11 @@ pub fn part_one(input: &str) -> Option<usize> {
         racetrack
             .iter()
             .flat_map(|(&coord, pos)| {
-                DIRECTIONS_4
+                Direction::ALL_4
                     .iter()
                     .filter_map(|&dir| {
-                        map.move_in_direction(coord, dir)
-                            .and_then(|cheat_coord| map.move_in_direction(cheat_coord, dir))
+                        map.step(coord, dir)
+                            .and_then(|cheat_coord| map.step(cheat_coord, dir))
                             .filter(|cheat_coord| {
                                 racetrack.get(cheat_coord).is_some_and(|&cheat_pos| {
                                     cheat_pos - (pos + HACK_TIME_PART_1) >= MIN_SAVING_PART_1
@@ -113,7 +111,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let map = parse_input(input);
+    let map = Grid::from_chars(input);
     let num_cheats = count_possible_cheats(&map, HACK_TIME_PART_2, MIN_SAVING_PART_2);
 
     Some(num_cheats)
@@ -125,13 +123,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(285));
     }
 }
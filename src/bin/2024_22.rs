@@ -2,7 +2,7 @@ use gxhash::{HashMap, HashMapExt};
 
 use itertools::Itertools;
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(2024, 22);
 
 #[inline]
 fn mix(secret: u64, value: u64) -> u64 {
@@ -95,15 +95,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(37327623));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 2).unwrap());
         assert_eq!(result, Some(23));
     }
 }
@@ -0,0 +1,7 @@
+//! Links all solutions into a single binary, so that `cargo all` and `cargo time` can run every day in one process.
+//! The list of solutions is generated by `build.rs`.
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    advent_of_code::template::registry::run(SOLUTIONS);
+}
@@ -0,0 +1,215 @@
+/// Compass directions on a grid and turns between them.
+///
+/// Directions follow the grid convention of `pathfinding`: north points to the previous row, east to the next column.
+use std::fmt::Display;
+
+use pathfinding::matrix::directions;
+
+/// One of the eight directions of a grid, in clockwise order starting at north.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+/// A turn relative to the current direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    /// 90° counter-clockwise.
+    Left,
+    /// 90° clockwise.
+    Right,
+    /// 180°.
+    Around,
+}
+
+impl Direction {
+    /// The four directions without diagonals, clockwise starting at north.
+    pub const ALL_4: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// All eight directions, clockwise starting at north.
+    pub const ALL_8: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    pub fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Direction::NE | Direction::SE | Direction::SW | Direction::NW
+        )
+    }
+
+    /// Rotate clockwise by `eighths` eighths of a full turn, i.e. 45° each.
+    pub fn rotate_cw(self, eighths: usize) -> Self {
+        Self::ALL_8[(self as usize + eighths) % 8]
+    }
+
+    /// Rotate counter-clockwise by `eighths` eighths of a full turn, i.e. 45° each.
+    pub fn rotate_ccw(self, eighths: usize) -> Self {
+        self.rotate_cw(8 - eighths % 8)
+    }
+
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.rotate_ccw(2),
+            Turn::Right => self.rotate_cw(2),
+            Turn::Around => self.rotate_cw(4),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(Turn::Around)
+    }
+
+    /// The offset of one step as `(row, column)`, like the constants of `pathfinding::matrix::directions`.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::N => directions::N,
+            Direction::NE => directions::NE,
+            Direction::E => directions::E,
+            Direction::SE => directions::SE,
+            Direction::S => directions::S,
+            Direction::SW => directions::SW,
+            Direction::W => directions::W,
+            Direction::NW => directions::NW,
+        }
+    }
+
+    /// An arrow pointing in the direction, e.g. `^` for north or `↗` for north-east.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::N => '^',
+            Direction::NE => '↗',
+            Direction::E => '>',
+            Direction::SE => '↘',
+            Direction::S => 'v',
+            Direction::SW => '↙',
+            Direction::W => '<',
+            Direction::NW => '↖',
+        }
+    }
+}
+
+impl From<Direction> for (isize, isize) {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl TryFrom<(isize, isize)> for Direction {
+    type Error = String;
+
+    fn try_from(offset: (isize, isize)) -> Result<Self, Self::Error> {
+        Self::ALL_8
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+            .ok_or_else(|| format!("{offset:?} is not a direction."))
+    }
+}
+
+/// Parse arrows (`^>v<`) and compass letters (`NESW`).
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' => Ok(Direction::N),
+            '>' | 'E' => Ok(Direction::E),
+            'v' | 'S' => Ok(Direction::S),
+            '<' | 'W' => Ok(Direction::W),
+            c => Err(format!("`{c}` is not a direction.")),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+/// Parse `L` and `R`.
+impl TryFrom<char> for Turn {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            c => Err(format!("`{c}` is not a turn.")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use pathfinding::matrix::directions;
+
+    use super::{Direction, Turn};
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::N.turn(Turn::Right), Direction::E);
+        assert_eq!(Direction::N.turn(Turn::Left), Direction::W);
+        assert_eq!(Direction::W.turn(Turn::Right), Direction::N);
+        assert_eq!(Direction::SE.turn(Turn::Around), Direction::NW);
+        assert_eq!(Direction::N.rotate_cw(1), Direction::NE);
+        assert_eq!(Direction::N.rotate_ccw(1), Direction::NW);
+        assert_eq!(Direction::E.opposite(), Direction::W);
+    }
+
+    #[test]
+    fn converts_pathfinding_directions() {
+        assert_eq!(Direction::N.offset(), directions::N);
+        assert_eq!(<(isize, isize)>::from(Direction::SW), directions::SW);
+
+        for (direction, offset) in Direction::ALL_4.into_iter().zip([
+            directions::N,
+            directions::E,
+            directions::S,
+            directions::W,
+        ]) {
+            assert_eq!(Direction::try_from(offset), Ok(direction));
+        }
+        assert!(Direction::try_from((2, 0)).is_err());
+    }
+
+    #[test]
+    fn parses_directions() {
+        let arrows: Result<Vec<_>, _> = "^>v<".chars().map(Direction::try_from).collect();
+        let compass: Result<Vec<_>, _> = "NESW".chars().map(Direction::try_from).collect();
+        assert_eq!(arrows, Ok(Direction::ALL_4.to_vec()));
+        assert_eq!(compass, Ok(Direction::ALL_4.to_vec()));
+        assert!(Direction::try_from('x').is_err());
+
+        assert_eq!(Direction::S.to_string(), "v");
+        assert_eq!(Turn::try_from('L'), Ok(Turn::Left));
+    }
+
+    #[test]
+    fn classifies_diagonals() {
+        let diagonals: Vec<_> = Direction::ALL_8
+            .into_iter()
+            .filter(|d| d.is_diagonal())
+            .collect();
+        assert_eq!(
+            diagonals,
+            vec![Direction::NE, Direction::SE, Direction::SW, Direction::NW]
+        );
+    }
+}
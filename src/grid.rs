@@ -0,0 +1,223 @@
+/// A two-dimensional grid of cells parsed from a character map, as found in many puzzle inputs.
+///
+/// `Grid` wraps a `pathfinding` [`Matrix`] and dereferences to it, so all of its methods (`get`, `items`, `move_in_direction`, …) are available.
+/// Positions are `(row, column)` tuples, like in [`Matrix`].
+use std::{
+    fmt::Display,
+    ops::{Deref, DerefMut},
+};
+
+use pathfinding::matrix::{directions, Matrix};
+
+use crate::direction::Direction;
+
+/// A position in a grid, as `(row, column)`.
+pub type Position = (usize, usize);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T>(Matrix<T>);
+
+impl<T> Grid<T> {
+    /// Parse a character map with one row per line, mapping each character to a cell.
+    ///
+    /// # Panics
+    ///
+    /// If the lines are not all of the same length.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        Self::from_rows(
+            input
+                .lines()
+                .map(|line| line.chars().map(&mut cell).collect::<Vec<_>>()),
+        )
+    }
+
+    /// Build a grid from rows of cells, e.g. if a character maps to several cells.
+    ///
+    /// # Panics
+    ///
+    /// If the rows are not all of the same length.
+    pub fn from_rows<R, C>(rows: R) -> Self
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoIterator<Item = T>,
+    {
+        Grid(Matrix::from_rows(rows).expect("rows of a grid should have the same length"))
+    }
+
+    pub fn into_inner(self) -> Matrix<T> {
+        self.0
+    }
+
+    /// The position of the first cell, in reading order, that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.items()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// The positions of all cells that match `predicate`, in reading order.
+    pub fn positions<'a>(
+        &'a self,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        self.items()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// Move one step in a direction, unless that leaves the grid.
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        self.move_in_direction(position, direction.offset())
+    }
+
+    /// The neighbours of a position that lie within the grid, optionally including diagonal ones.
+    pub fn neighbours(
+        &self,
+        position: Position,
+        diagonals: bool,
+    ) -> impl Iterator<Item = Position> + '_ {
+        self.0.neighbours(position, diagonals)
+    }
+
+    /// The neighbours of a position, wrapping around the edges of the grid as if it was a torus.
+    pub fn wrapping_neighbours(
+        &self,
+        position: Position,
+        diagonals: bool,
+    ) -> impl Iterator<Item = Position> + '_ {
+        let directions: &[(isize, isize)] = if diagonals {
+            &directions::DIRECTIONS_8
+        } else {
+            &directions::DIRECTIONS_4
+        };
+
+        directions
+            .iter()
+            .map(move |&direction| self.wrapping_move(position, direction))
+    }
+
+    /// Move one step in a direction, wrapping around the edges of the grid.
+    pub fn wrapping_move(&self, (row, column): Position, (dr, dc): (isize, isize)) -> Position {
+        let wrap = |value: usize, delta: isize, len: usize| {
+            #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+            let wrapped = (value as isize + delta).rem_euclid(len as isize) as usize;
+            wrapped
+        };
+
+        (wrap(row, dr, self.rows), wrap(column, dc, self.columns))
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The position of the first cell, in reading order, that equals `marker`, e.g. the start `'S'`.
+    pub fn find(&self, marker: &T) -> Option<Position> {
+        self.position(|cell| cell == marker)
+    }
+
+    /// The positions of all cells that equal `marker`, in reading order.
+    pub fn find_all<'a>(&'a self, marker: &'a T) -> impl Iterator<Item = Position> + 'a {
+        self.positions(move |cell| cell == marker)
+    }
+}
+
+impl Grid<char> {
+    /// Parse a character map into a grid of its characters.
+    pub fn from_chars(input: &str) -> Self {
+        Self::parse(input, |c| c)
+    }
+}
+
+impl<T> Deref for Grid<T> {
+    type Target = Matrix<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Grid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Matrix<T>> for Grid<T> {
+    fn from(matrix: Matrix<T>) -> Self {
+        Grid(matrix)
+    }
+}
+
+/// Renders the grid with one line per row, e.g. to print intermediate states with `println!("{grid}")`.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::direction::Direction;
+
+    const MAP: &str = "#S.\n.#E\n..#\n";
+
+    #[test]
+    fn parses_cells() {
+        let grid = Grid::parse(MAP, |c| c == '#');
+        assert_eq!((grid.rows, grid.columns), (3, 3));
+        assert!(grid[(0, 0)]);
+        assert!(!grid[(0, 1)]);
+        assert_eq!(grid.positions(|&wall| wall).count(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_ragged_rows() {
+        Grid::from_chars("##\n#\n");
+    }
+
+    #[test]
+    fn finds_markers() {
+        let grid = Grid::from_chars(MAP);
+        assert_eq!(grid.find(&'S'), Some((0, 1)));
+        assert_eq!(grid.find(&'E'), Some((1, 2)));
+        assert_eq!(grid.find(&'@'), None);
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = Grid::from_chars(MAP);
+
+        let mut bounded: Vec<_> = grid.neighbours((0, 0), false).collect();
+        bounded.sort_unstable();
+        assert_eq!(bounded, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours((1, 1), true).count(), 8);
+
+        let mut wrapping: Vec<_> = grid.wrapping_neighbours((0, 0), false).collect();
+        wrapping.sort_unstable();
+        assert_eq!(wrapping, vec![(0, 1), (0, 2), (1, 0), (2, 0)]);
+        assert_eq!(grid.wrapping_neighbours((0, 0), true).count(), 8);
+        assert_eq!(grid.wrapping_move((2, 2), (1, 1)), (0, 0));
+
+        assert_eq!(grid.step((0, 1), Direction::S), Some((1, 1)));
+        assert_eq!(grid.step((0, 1), Direction::N), None);
+    }
+
+    #[test]
+    fn renders_grid() {
+        let grid = Grid::parse(MAP, |c| if c == '#' { '█' } else { c });
+        assert_eq!(grid.to_string(), "█S.\n.█E\n..█\n");
+    }
+}
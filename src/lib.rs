@@ -1,3 +1,8 @@
+pub mod direction;
+pub mod grid;
+pub mod parse;
+pub mod point;
 pub mod template;
+pub mod vm;
 
 // Use this file to add helper functions and additional modules.
@@ -1,40 +1,85 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, answer, compare, download, examples, read, readme, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
+use std::path::Path;
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
+use advent_of_code::template::{Error, Execution, RunOptions};
 use std::process;
 
+#[cfg(feature = "today")]
+use advent_of_code::template::{Day, PuzzleId, Year};
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use std::time::Duration;
+
+    use advent_of_code::template::commands::{compare, scaffold, solve};
+    use advent_of_code::template::{
+        Baseline, Day, OutputFormat, PuzzleId, TableOptions, Timeouts, Year,
+    };
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Answer {
+            puzzle: PuzzleId,
+            part: u8,
+            answer: String,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Readme {
+            year: Year,
+            options: TableOptions,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            overwrite: bool,
+            answer_type: String,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: String,
+            answer_type: String,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            alloc: bool,
+            submit: Option<solve::SubmitArgs>,
+            format: OutputFormat,
+            input_args: solve::InputArgs,
         },
         All {
+            year: Year,
             release: bool,
+            isolated: bool,
+            jobs: usize,
+            format: OutputFormat,
+            timeouts: Timeouts,
+            alloc: bool,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
-            store: bool,
+            store: Option<TableOptions>,
+            isolated: bool,
+            baseline: Baseline,
+            threshold: f64,
+            timeouts: Timeouts,
+            alloc: bool,
+        },
+        Compare {
+            year: Year,
+            day: Option<Day>,
+            rev: String,
+            rounds: usize,
         },
         #[cfg(feature = "today")]
         Today,
@@ -45,45 +90,78 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: year(&mut args)?,
                 release: args.contains("--release"),
+                isolated: args.contains("--isolated"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                timeouts: timeouts(&mut args, Timeouts::ALL_DEFAULT)?,
+                alloc: args.contains("--alloc"),
             },
             Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+                let year = year(&mut args)?;
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+                if let Some(rev) = args.opt_value_from_str("--compare")? {
+                    let rounds = args
+                        .opt_value_from_str("--rounds")?
+                        .unwrap_or(compare::DEFAULT_ROUNDS);
+
+                    AppArguments::Compare {
+                        year,
+                        day: args.opt_free_from_str()?,
+                        rev,
+                        rounds,
+                    }
+                } else {
+                    time_args(&mut args, year)?
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
+            },
+            Some("answer") => AppArguments::Answer {
+                puzzle: puzzle(&mut args)?,
+                part: args.free_from_str()?,
+                answer: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
+            },
+            Some("readme") => AppArguments::Readme {
+                year: year(&mut args)?,
+                options: table_options(&mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: puzzle(&mut args)?,
+                overwrite: args.contains("--overwrite"),
+                answer_type: args
+                    .opt_value_from_str("--answer-type")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_ANSWER_TYPE.to_string()),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.to_string()),
+                answer_type: args
+                    .opt_value_from_str("--answer-type")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_ANSWER_TYPE.to_string()),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
+                submit: submit_args(&mut args)?,
                 dhat: args.contains("--dhat"),
+                alloc: args.contains("--alloc"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                input_args: input_args(&mut args)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some(x) => return Err(format!("unknown command: {x}").into()),
+            None => return Err("no command specified.".into()),
         };
 
         let remaining = args.finish();
@@ -93,52 +171,260 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Read the options of `time` when it is not comparing against a revision.
+    fn time_args(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let all = args.contains("--all");
+        let store = if args.contains("--store") {
+            Some(table_options(args)?)
+        } else {
+            None
+        };
+        let isolated = args.contains("--isolated");
+        let baseline = args.opt_value_from_str("--baseline")?.unwrap_or_default();
+        let threshold = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+        let timeouts = timeouts(args, Timeouts::default())?;
+        let alloc = args.contains("--alloc");
+
+        Ok(AppArguments::Time {
+            year,
+            all,
+            day: args.opt_free_from_str()?,
+            store,
+            isolated,
+            baseline,
+            threshold,
+            timeouts,
+            alloc,
+        })
+    }
+
+    /// Read the presentation options of the readme benchmark table, see `cargo time --store` and `cargo readme`.
+    fn table_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<TableOptions, Box<dyn std::error::Error>> {
+        Ok(TableOptions {
+            order: args.opt_value_from_str("--sort")?.unwrap_or_default(),
+            percent: args.contains("--percent"),
+            bars: args.contains("--bars"),
+            highlight: args.opt_value_from_str("--highlight")?.unwrap_or(0),
+        })
+    }
+
+    /// Read the `--timeout` and `--part-timeout` options in seconds, where `0` disables a timeout.
+    fn timeouts(
+        args: &mut pico_args::Arguments,
+        default: Timeouts,
+    ) -> Result<Timeouts, Box<dyn std::error::Error>> {
+        let mut seconds = |name: &'static str, default: Option<Duration>| {
+            let value: Option<f64> = args.opt_value_from_str(name)?;
+            Ok::<_, Box<dyn std::error::Error>>(match value {
+                Some(secs) if secs > 0.0 => Some(Duration::from_secs_f64(secs)),
+                Some(_) => None,
+                None => default,
+            })
+        };
+
+        Ok(Timeouts {
+            day: seconds("--timeout", default.day)?,
+            part: seconds("--part-timeout", default.part)?,
+        })
+    }
+
+    /// Read the year from the `--year` option, falling back to the `AOC_YEAR` environment variable.
+    fn year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        args.opt_value_from_str("--year")?
+            .or_else(Year::from_env)
+            .ok_or_else(|| "no year specified, use `--year` or set `AOC_YEAR`.".into())
+    }
+
+    /// Read the input options of `solve`: `--input <path>`, `--example [N]` and `-` for stdin.
+    fn input_args(
+        args: &mut pico_args::Arguments,
+    ) -> Result<solve::InputArgs, Box<dyn std::error::Error>> {
+        let input = args.opt_value_from_str("--input")?;
+        let example = if args.contains("--example") {
+            Some(args.opt_free_from_str()?)
+        } else {
+            None
+        };
+        let stdin = args.contains("-");
+
+        Ok(solve::InputArgs {
+            input,
+            example,
+            stdin,
+        })
+    }
+
+    /// Read the submission options of `solve`: `--submit <part>` and `--ignore-ledger`.
+    fn submit_args(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<solve::SubmitArgs>, Box<dyn std::error::Error>> {
+        let part = args.opt_value_from_str("--submit")?;
+        let ignore_ledger = args.contains("--ignore-ledger");
+
+        Ok(part.map(|part| solve::SubmitArgs {
+            part,
+            ignore_ledger,
+        }))
+    }
+
+    /// Read a puzzle from the `--year` option and the day argument.
+    fn puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = year(args)?;
+        let day: Day = args.free_from_str()?;
+        Ok(PuzzleId::new(year, day))
+    }
 }
 
 fn main() {
-    match parse() {
+    let args = match parse() {
+        Ok(args) => args,
         Err(err) => {
+            let err = Error::Usage(err.to_string());
             eprintln!("Error: {err}");
-            std::process::exit(1);
+            process::exit(err.exit_code());
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+    };
+
+    if let Err(err) = run(args) {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn execution(is_isolated: bool) -> Execution {
+    if is_isolated {
+        Execution::Isolated
+    } else {
+        Execution::Registry
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All {
+            year,
+            release,
+            isolated,
+            jobs,
+            format,
+            timeouts,
+            alloc,
+        } => {
+            // days that run in parallel in the runner process would count each other's allocations.
+            if alloc && jobs > 1 && !isolated {
+                return Err(Error::Usage(
+                    "`--alloc` can't be combined with `--jobs` unless the days run `--isolated`."
+                        .into(),
+                ));
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+
+            all::handle(
+                year,
+                &RunOptions {
+                    is_release: release,
+                    format,
+                    execution: execution(isolated),
+                    jobs,
+                    timeouts,
+                    alloc_stats: alloc,
+                    ..RunOptions::default()
+                },
+            )
+        }
+        AppArguments::Time {
+            year,
+            day,
+            all,
+            store,
+            isolated,
+            baseline,
+            threshold,
+            timeouts,
+            alloc,
+        } => time::handle(
+            year,
+            day,
+            all,
+            store,
+            &RunOptions {
+                execution: execution(isolated),
+                timeouts,
+                alloc_stats: alloc,
+                ..RunOptions::default()
+            },
+            baseline,
+            threshold,
+        ),
+        AppArguments::Compare {
+            year,
+            day,
+            rev,
+            rounds,
+        } => compare::handle(year, day, &rev, rounds),
+        AppArguments::Download { puzzle } => download::handle(puzzle),
+        AppArguments::Answer {
+            puzzle,
+            part,
+            answer,
+        } => answer::handle(puzzle, part, &answer),
+        AppArguments::Read { puzzle } => read::handle(puzzle),
+        AppArguments::Readme { year, options } => readme::handle(year, &options),
+        AppArguments::Examples {
+            puzzle,
+            overwrite,
+            answer_type,
+        } => examples::handle(puzzle, overwrite, &answer_type),
+        AppArguments::Scaffold {
+            puzzle,
+            download,
+            overwrite,
+            template,
+            answer_type,
+        } => {
+            // download first, so that the title of the puzzle is available to the template.
+            if download {
+                download::handle(puzzle)?;
+            }
+            scaffold::handle(puzzle, overwrite, &template, &answer_type)?;
+            if Path::new(&puzzle.puzzle_path()).exists() {
+                examples::handle(puzzle, false, &answer_type)?;
             }
+            Ok(())
+        }
+        AppArguments::Solve {
+            puzzle,
+            release,
+            dhat,
+            alloc,
+            submit,
+            format,
+            input_args,
+        } => solve::handle(puzzle, release, dhat, alloc, submit, format, input_args),
+        #[cfg(feature = "today")]
+        AppArguments::Today => match Year::today().zip(Day::today()) {
+            Some((year, day)) => {
+                let puzzle = PuzzleId::new(year, day);
+                download::handle(puzzle)?;
+                scaffold::handle(
+                    puzzle,
+                    false,
+                    scaffold::DEFAULT_TEMPLATE,
+                    scaffold::DEFAULT_ANSWER_TYPE,
+                )?;
+                examples::handle(puzzle, false, scaffold::DEFAULT_ANSWER_TYPE)?;
+                read::handle(puzzle)
+            }
+            None => Err(Error::Usage(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day."
+                    .into(),
+            )),
         },
-    };
+    }
 }
@@ -0,0 +1,385 @@
+/// Parser combinators for the recurring shapes of puzzle inputs: numbers, lists, coordinates, lines, blocks and grids.
+///
+/// All parsers return a `nom_supreme` [`ErrorTree`], so failures keep the location of the bad input and any attached `.context(…)` labels.
+/// Lines and blocks are parsed strictly: once a line has started, it has to match the item parser, instead of silently ending the list.
+/// Failures of [`parse_or_report`] are turned into a [`ParseReport`] that points at the offending line.
+use std::{error::Error, fmt::Display, panic, str::FromStr};
+
+use nom::{
+    branch::alt,
+    bytes::complete::is_not,
+    character::complete::{char, digit1, line_ending, multispace0, none_of, one_of, space1},
+    combinator::{all_consuming, cut, eof, map_res, not, opt, recognize, value},
+    multi::{many1, separated_list1},
+    sequence::{pair, separated_pair, terminated, tuple},
+    Finish, Parser,
+};
+use nom_supreme::{
+    error::{BaseErrorKind, ErrorTree, Expectation, StackContext},
+    tag::complete::tag,
+    ParserExt,
+};
+use pathfinding::matrix::Matrix;
+
+use crate::grid::Grid;
+
+/// The result of the parsers in this module.
+pub type IResult<'a, O> = nom::IResult<&'a str, O, ErrorTree<&'a str>>;
+
+/// Run `parser` on the whole input, allowing only trailing whitespace after it.
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> Result<O, ErrorTree<&'a str>> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+}
+
+/// Run `parser` on the whole input like [`parse_all`].
+///
+/// # Panics
+///
+/// With a [`ParseReport`] as payload if the input does not match, which the solution runner prints instead of a backtrace.
+pub fn parse_or_report<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> O {
+    parse_all(input, parser)
+        .unwrap_or_else(|error| panic::panic_any(ParseReport::new(input, &error)))
+}
+
+/// An unsigned number, e.g. `42`.
+pub fn unsigned<T>(input: &str) -> IResult<'_, T>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    digit1.parse_from_str().parse(input)
+}
+
+/// A number with an optional sign, e.g. `-3` or `+7`.
+pub fn signed<T>(input: &str) -> IResult<'_, T>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    recognize(pair(opt(one_of("+-")), digit1))
+        .parse_from_str()
+        .parse(input)
+}
+
+/// Items separated by spaces or tabs on a single line, e.g. `7 6 4 2 1`.
+pub fn whitespace_separated<'a, O>(
+    item: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(space1, item)
+}
+
+/// Items separated by commas, e.g. `75,47,61,53,29`.
+pub fn comma_separated<'a, O>(
+    item: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(char(','), item)
+}
+
+/// A pair of signed numbers separated by a comma, e.g. `x,y` in `p=0,4`.
+pub fn coordinates<T>(input: &str) -> IResult<'_, (T, T)>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    separated_pair(signed, char(','), signed)(input)
+}
+
+/// The text before a `: `, e.g. `Button A` in `Button A: X+94, Y+34`.
+pub fn label(input: &str) -> IResult<'_, &str> {
+    is_not(":\r\n")(input)
+}
+
+/// A key and a value separated by `: `, e.g. `key_value(unsigned, whitespace_separated(unsigned))` for `190: 10 19`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, K, ErrorTree<&'a str>>,
+    value: impl Parser<&'a str, V, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, (K, V)> {
+    separated_pair(key, tag(": "), value)
+}
+
+/// A line ending that is followed by another line, i.e. neither by a blank line nor by the end of the input.
+fn line_separator(input: &str) -> IResult<'_, ()> {
+    value((), terminated(line_ending, not(alt((line_ending, eof)))))(input)
+}
+
+/// One item per line, without the final line ending.
+pub fn lines<'a, O>(
+    item: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(line_separator, cut(item))
+}
+
+/// Blocks separated by blank lines, e.g. `blocks(lines(…))`.
+pub fn blocks<'a, O>(
+    block: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(blank_line, cut(block))
+}
+
+/// An empty line between two blocks, e.g. between the rules and updates of day 5.
+pub fn blank_line(input: &str) -> IResult<'_, ()> {
+    value((), tuple((line_ending, line_ending)))(input)
+}
+
+/// A character map with one row per line, parsing each character with `cell`.
+pub fn grid<'a, T>(
+    cell: impl Parser<&'a str, T, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Grid<T>> {
+    let mut parser = map_res(lines(many1(cell)), |rows| {
+        Matrix::from_rows(rows).map(Grid::from)
+    })
+    .context("All rows of a grid should have the same length");
+
+    move |input| parser.parse(input)
+}
+
+/// A character map with one row per line, keeping the characters.
+pub fn char_grid(input: &str) -> IResult<'_, Grid<char>> {
+    grid(none_of("\r\n"))(input)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A human-readable description of a parse failure: where it happened, what was expected and the `.context(…)` labels around it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseReport {
+    /// 1-based line of the failure.
+    pub line: usize,
+    /// 1-based column of the failure, in characters.
+    pub column: usize,
+    /// The offending line, without its line ending.
+    pub source_line: String,
+    /// What the parser expected at the location, e.g. `expected ", "`.
+    pub expected: String,
+    /// The context labels, innermost first, e.g. `Each machine should have 3 lines`.
+    pub contexts: Vec<&'static str>,
+}
+
+impl ParseReport {
+    /// Locate `error` in the `input` it was produced for. Of several alternatives, the one that got furthest is reported.
+    pub fn new(input: &str, error: &ErrorTree<&str>) -> Self {
+        let (location, expected, contexts) = deepest_error(error);
+
+        let offset = input.len().saturating_sub(location.len());
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map_or(0, |ix| ix + 1);
+
+        ParseReport {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            source_line: input[line_start..].lines().next().unwrap_or("").into(),
+            expected,
+            contexts,
+        }
+    }
+}
+
+/// The location, expectation and contexts of the error that consumed the most input.
+fn deepest_error<'a>(error: &ErrorTree<&'a str>) -> (&'a str, String, Vec<&'static str>) {
+    match error {
+        ErrorTree::Base { location, kind } => (location, describe(kind), vec![]),
+        ErrorTree::Stack { base, contexts } => {
+            let (location, expected, mut labels) = deepest_error(base);
+            labels.extend(contexts.iter().filter_map(|(_, context)| match context {
+                StackContext::Context(label) => Some(*label),
+                StackContext::Kind(_) => None,
+            }));
+            (location, expected, labels)
+        }
+        ErrorTree::Alt(alternatives) => {
+            let errors: Vec<_> = alternatives.iter().map(deepest_error).collect();
+            let shortest = errors.iter().map(|(location, ..)| location.len()).min();
+
+            let mut deepest = errors
+                .into_iter()
+                .filter(|(location, ..)| Some(location.len()) == shortest);
+            let (location, expected, contexts) =
+                deepest.next().unwrap_or(("", String::new(), vec![]));
+
+            // alternatives that failed at the same location are listed together.
+            let expected = deepest.fold(expected, |acc, (_, other, _)| format!("{acc} or {other}"));
+            (location, expected, contexts)
+        }
+    }
+}
+
+fn describe(kind: &BaseErrorKind<&str, Box<dyn Error + Send + Sync>>) -> String {
+    match kind {
+        BaseErrorKind::Expected(Expectation::Eof) => "expected the end of the input".into(),
+        BaseErrorKind::External(error) => error.to_string(),
+        kind => kind.to_string(),
+    }
+}
+
+/// Renders the report like a compiler error, e.g.
+///
+/// ```text
+/// parse error at line 2, column 2: expected ','
+///   |
+/// 2 | 3;4
+///   |  ^
+///   = in: Each machine should have 3 lines
+/// ```
+impl Display for ParseReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(
+            f,
+            "parse error at line {}, column {}: {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))?;
+        for context in &self.contexts {
+            write!(f, "\n{gutter} = in: {context}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseReport {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::panic;
+
+    use nom::{
+        character::complete::{char, line_ending, space1},
+        sequence::{pair, preceded, separated_pair},
+    };
+    use nom_supreme::{error::ErrorTree, tag::complete::tag, ParserExt};
+
+    use super::{
+        blank_line, blocks, char_grid, comma_separated, coordinates, key_value, label, lines,
+        parse_all, parse_or_report, signed, unsigned, whitespace_separated, ParseReport,
+    };
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(unsigned::<u32>("42 rest").unwrap(), (" rest", 42));
+        assert!(unsigned::<u32>("-42").is_err());
+        assert_eq!(signed::<i32>("-42").unwrap(), ("", -42));
+        assert_eq!(signed::<i32>("+7").unwrap(), ("", 7));
+        assert!(unsigned::<u8>("256").is_err());
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(
+            parse_all("7 6 4 2 1", whitespace_separated(unsigned::<u32>)).unwrap(),
+            vec![7, 6, 4, 2, 1]
+        );
+        assert_eq!(
+            parse_all("-1,2,-3", comma_separated(signed::<i64>)).unwrap(),
+            vec![-1, 2, -3]
+        );
+        assert_eq!(
+            parse_all("p=0,-4", coordinates::<i32>.preceded_by(tag("p="))).unwrap(),
+            (0, -4)
+        );
+    }
+
+    #[test]
+    fn parses_lines_and_blocks() {
+        let pair = || separated_pair(unsigned::<u32>, space1, unsigned::<u32>);
+        assert_eq!(
+            parse_all("3   4\n4   3\n", lines(pair())).unwrap(),
+            vec![(3, 4), (4, 3)]
+        );
+
+        let input = "1|2\n3|4\n\n1,2\n3\n";
+        let rule = separated_pair(unsigned::<u32>, char('|'), unsigned::<u32>);
+        let (rules, updates) = parse_all(
+            input,
+            separated_pair(
+                lines(rule),
+                blank_line,
+                lines(comma_separated(unsigned::<u32>)),
+            ),
+        )
+        .unwrap();
+        assert_eq!(rules, vec![(1, 2), (3, 4)]);
+        assert_eq!(updates, vec![vec![1, 2], vec![3]]);
+
+        assert_eq!(
+            parse_all("1\n2\n\n3\n", blocks(lines(unsigned::<u32>))).unwrap(),
+            vec![vec![1, 2], vec![3]]
+        );
+    }
+
+    #[test]
+    fn fails_at_the_bad_line() {
+        let error = parse_all("1,2\n3;4\n5,6\n", lines(coordinates::<i32>)).unwrap_err();
+        let ErrorTree::Base { location, .. } = error else {
+            panic!("expected a single error, got {error:?}");
+        };
+        assert_eq!(location, ";4\n5,6\n");
+    }
+
+    #[test]
+    fn reports_the_bad_line() {
+        let input = "1,2\n3;4\n";
+        let error = parse_all(input, lines(coordinates::<i32>)).unwrap_err();
+        let report = ParseReport::new(input, &error);
+
+        assert_eq!((report.line, report.column), (2, 2));
+        assert_eq!(report.source_line, "3;4");
+        assert_eq!(
+            report.to_string(),
+            "parse error at line 2, column 2: expected ','\n  |\n2 | 3;4\n  |  ^"
+        );
+    }
+
+    #[test]
+    fn reports_contexts() {
+        let input = "1\n2\n\n3\nx\n";
+        let block = pair(unsigned::<u32>, preceded(line_ending, unsigned::<u32>))
+            .context("Each block should have 2 lines");
+
+        let payload = panic::catch_unwind(|| parse_or_report(input, blocks(block))).unwrap_err();
+        let report = payload.downcast_ref::<ParseReport>().unwrap();
+
+        assert_eq!((report.line, report.column), (5, 1));
+        assert_eq!(report.expected, "expected an ascii digit");
+        assert_eq!(report.contexts, vec!["Each block should have 2 lines"]);
+        assert!(report
+            .to_string()
+            .ends_with("5 | x\n  | ^\n  = in: Each block should have 2 lines"));
+    }
+
+    #[test]
+    fn parses_key_values() {
+        assert_eq!(
+            parse_all(
+                "190: 10 19",
+                key_value(unsigned::<u64>, whitespace_separated(unsigned::<u64>))
+            )
+            .unwrap(),
+            (190, vec![10, 19])
+        );
+        assert_eq!(
+            label("Button A: X+94, Y+34").unwrap(),
+            (": X+94, Y+34", "Button A")
+        );
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = parse_all("#S.\n.#E\n", char_grid).unwrap();
+        assert_eq!((grid.rows, grid.columns), (2, 3));
+        assert_eq!(grid.find(&'E'), Some((1, 2)));
+        assert!(parse_all("##\n#\n", char_grid).is_err());
+    }
+}
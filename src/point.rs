@@ -0,0 +1,220 @@
+/// A two-dimensional point with arithmetic and distances, e.g. for positions and velocities.
+///
+/// `y` grows downwards like the rows of a grid, so that [`Direction::N`] decreases `y`.
+/// Grid positions are `(row, column)` tuples, use [`Point::from_position`] and [`Point::position`] to convert.
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use num::traits::Euclid;
+
+use crate::direction::Direction;
+use crate::grid::Position;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Copy + PartialOrd + Sub<Output = T>> Point<T> {
+    /// The sum of the distances along both axes.
+    pub fn manhattan(self, other: Self) -> T
+    where
+        T: Add<Output = T>,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The larger of the distances along both axes, i.e. the number of 8-way steps between two points.
+    pub fn chebyshev(self, other: Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Point<T> {
+    /// Rotate 90° clockwise around the origin.
+    pub fn rotate_cw(self) -> Self {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Rotate 90° counter-clockwise around the origin.
+    pub fn rotate_ccw(self) -> Self {
+        Point::new(self.y, -self.x)
+    }
+}
+
+impl<T: Euclid> Point<T> {
+    /// Wrap both coordinates into `0..modulus`, e.g. to move on a grid that wraps around its edges.
+    pub fn rem_euclid(&self, modulus: &Self) -> Self {
+        Point::new(self.x.rem_euclid(&modulus.x), self.y.rem_euclid(&modulus.y))
+    }
+}
+
+impl Point<usize> {
+    /// Convert a `(row, column)` grid position.
+    pub fn from_position((row, column): Position) -> Self {
+        Point::new(column, row)
+    }
+
+    /// The `(row, column)` grid position of the point.
+    pub fn position(self) -> Position {
+        (self.y, self.x)
+    }
+
+    /// Move one step in a direction, unless that leaves the non-negative quadrant.
+    pub fn checked_step(self, direction: Direction) -> Option<Self> {
+        let (dy, dx) = direction.offset();
+        Some(Point::new(
+            self.x.checked_add_signed(dx)?,
+            self.y.checked_add_signed(dy)?,
+        ))
+    }
+}
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// The unit step in a direction.
+impl<T: From<i8>> From<Direction> for Point<T> {
+    fn from(direction: Direction) -> Self {
+        let (dy, dx) = direction.offset();
+        #[allow(clippy::cast_possible_truncation)]
+        Point::new(T::from(dx as i8), T::from(dy as i8))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Add<Output = T> + From<i8>> Add<Direction> for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + Point::from(rhs)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+/// Formats the point as `x,y`, like puzzle inputs do.
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Point;
+    use crate::direction::Direction;
+
+    #[test]
+    fn computes_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a + b, Point::new(-2, 7));
+        assert_eq!(a - b, Point::new(4, -3));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a + Direction::N, Point::new(1, 1));
+        assert_eq!(a + Direction::SE, Point::new(2, 3));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        assert_eq!(
+            Point::new(-1, 12).rem_euclid(&Point::new(11, 7)),
+            Point::new(10, 5)
+        );
+    }
+
+    #[test]
+    fn computes_distances() {
+        let a = Point::new(1_usize, 2);
+        let b = Point::new(4, 0);
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+        assert_eq!(Point::new(-2, 3).manhattan(Point::new(2, -3)), 10);
+    }
+
+    #[test]
+    fn rotates() {
+        let east: Point<i32> = Direction::E.into();
+        assert_eq!(east.rotate_cw(), Direction::S.into());
+        assert_eq!(east.rotate_ccw(), Direction::N.into());
+        assert_eq!(Point::new(2, 1).rotate_cw().rotate_ccw(), Point::new(2, 1));
+    }
+
+    #[test]
+    fn converts_grid_positions() {
+        let point = Point::from_position((3, 5));
+        assert_eq!(point, Point::new(5, 3));
+        assert_eq!(point.position(), (3, 5));
+        assert_eq!(point.checked_step(Direction::W), Some(Point::new(4, 3)));
+        assert_eq!(Point::new(0_usize, 0).checked_step(Direction::N), None);
+        assert_eq!(Point::new(1, 2).to_string(), "1,2");
+    }
+}
@@ -0,0 +1,110 @@
+/// Store of known-correct answers, used to verify solutions after they changed.
+/// Answers are kept as one file per part, e.g. `data/2024/answers/01-2.txt`.
+use std::{fmt::Display, fs, io, path::Path};
+
+use crate::template::PuzzleId;
+
+static ANSWERS_FOLDER: &str = "answers";
+
+/// The result of comparing an answer against the store.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum AnswerCheck {
+    /// The answer matches the known answer.
+    Correct,
+    /// The answer differs from the known answer.
+    Wrong { expected: String },
+    /// There is no known answer for this part.
+    #[default]
+    Unknown,
+}
+
+impl AnswerCheck {
+    /// Compare an answer against a known answer, if any.
+    pub fn compare(expected: Option<&str>, answer: Option<&str>) -> Self {
+        match expected {
+            None => AnswerCheck::Unknown,
+            Some(expected) if answer.is_some_and(|a| a.trim() == expected) => AnswerCheck::Correct,
+            Some(expected) => AnswerCheck::Wrong {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, AnswerCheck::Wrong { .. })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnswerCheck::Correct => "correct",
+            AnswerCheck::Wrong { .. } => "wrong",
+            AnswerCheck::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for AnswerCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerCheck::Wrong { expected } => write!(f, "wrong, expected `{expected}`"),
+            check => f.write_str(check.as_str()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[must_use]
+pub fn get_answer_path(puzzle: PuzzleId, part: u8) -> String {
+    puzzle.data_part_path(ANSWERS_FOLDER, part)
+}
+
+/// Read the known answer of a part, if any.
+pub fn read(puzzle: PuzzleId, part: u8) -> Option<String> {
+    fs::read_to_string(get_answer_path(puzzle, part))
+        .ok()
+        .map(|answer| answer.trim().to_string())
+        .filter(|answer| !answer.is_empty())
+}
+
+/// Store the known answer of a part, replacing a previous answer.
+pub fn store(puzzle: PuzzleId, part: u8, answer: &str) -> io::Result<()> {
+    let path = get_answer_path(puzzle, part);
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", answer.trim()))
+}
+
+/// Compare an answer against the known answer of a part.
+pub fn check(puzzle: PuzzleId, part: u8, answer: Option<&str>) -> AnswerCheck {
+    AnswerCheck::compare(read(puzzle, part).as_deref(), answer)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::AnswerCheck;
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(
+            AnswerCheck::compare(Some("42"), Some("42")),
+            AnswerCheck::Correct
+        );
+        assert_eq!(
+            AnswerCheck::compare(Some("42"), Some("43")),
+            AnswerCheck::Wrong {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(AnswerCheck::compare(None, Some("42")), AnswerCheck::Unknown);
+    }
+
+    #[test]
+    fn treats_missing_answers_as_wrong() {
+        assert!(AnswerCheck::compare(Some("42"), None).is_wrong());
+        assert_eq!(AnswerCheck::compare(None, None), AnswerCheck::Unknown);
+    }
+}
@@ -0,0 +1,412 @@
+#[derive(Debug, PartialEq)]
+enum Node {
+    Element {
+        name: String,
+        class: Option<String>,
+        href: Option<String>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+const VOID_ELEMENTS: [&str; 7] = ["br", "hr", "img", "input", "link", "meta", "wbr"];
+
+/// Convert the HTML of a puzzle page to the markdown stored in `data/puzzles`.
+///
+/// Only the puzzle descriptions (`<article class="day-desc">`) and the "Your puzzle answer was" paragraphs are kept.
+/// Code blocks become fenced blocks, `<code>` becomes inline code and `<em>` becomes `*emphasis*`.
+/// Emphasized code, which the puzzles use to highlight answers, is rendered as ``*`answer`*``.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    let root = parse(html);
+    let main = find_element(&root, "main").unwrap_or(&root);
+
+    let mut blocks: Vec<String> = vec![];
+    collect_blocks(main, &mut blocks);
+
+    let mut markdown = blocks.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// Read the title of a puzzle from its markdown, e.g. `Day 1: Historian Hysteria`.
+pub fn puzzle_title(markdown: &str) -> Option<String> {
+    markdown
+        .lines()
+        .find_map(|line| line.strip_prefix("## --- Day "))
+        .map(|title| format!("Day {}", title.trim_end_matches('-').trim()))
+}
+
+fn collect_blocks(node: &Node, blocks: &mut Vec<String>) {
+    let Node::Element {
+        name,
+        class,
+        children,
+        ..
+    } = node
+    else {
+        return;
+    };
+
+    if name == "article" && class.as_deref().is_some_and(|c| c.contains("day-desc")) {
+        children
+            .iter()
+            .for_each(|child| render_block(child, blocks));
+    } else if name == "p" && plain_text(node).starts_with("Your puzzle answer was") {
+        blocks.push(render_inline(node).trim().to_string());
+    } else {
+        children
+            .iter()
+            .for_each(|child| collect_blocks(child, blocks));
+    }
+}
+
+fn render_block(node: &Node, blocks: &mut Vec<String>) {
+    match node {
+        Node::Text(text) => {
+            if !text.trim().is_empty() {
+                blocks.push(text.trim().to_string());
+            }
+        }
+        Node::Element { name, children, .. } => match name.as_str() {
+            "h2" => blocks.push(format!("## {}", plain_text(node).trim())),
+            "pre" => {
+                let code = plain_text(node);
+                blocks.push(format!("```\n{}\n```", code.trim_end_matches('\n')));
+            }
+            "ul" | "ol" => {
+                let items: Vec<String> = children
+                    .iter()
+                    .filter(|child| matches!(child, Node::Element { name, .. } if name == "li"))
+                    .map(|child| format!("- {}", render_inline(child).trim()))
+                    .collect();
+                blocks.push(items.join("\n"));
+            }
+            _ => {
+                let text = render_inline(node);
+                if !text.trim().is_empty() {
+                    blocks.push(text.trim().to_string());
+                }
+            }
+        },
+    }
+}
+
+fn render_inline(node: &Node) -> String {
+    match node {
+        Node::Text(text) => text.replace('\n', " "),
+        Node::Element {
+            name,
+            href,
+            children,
+            ..
+        } => {
+            let inner: String = children.iter().map(render_inline).collect();
+            match name.as_str() {
+                "code" => {
+                    let code = plain_text(node);
+                    let is_emphasized = matches!(
+                        children.as_slice(),
+                        [Node::Element { name, .. }] if name == "em"
+                    );
+                    if is_emphasized {
+                        format!("*`{code}`*")
+                    } else {
+                        format!("`{code}`")
+                    }
+                }
+                "em" => format!("*{inner}*"),
+                "a" => match href {
+                    Some(href) => format!("[{inner}]({href})"),
+                    None => inner,
+                },
+                "br" => "\n".into(),
+                _ => inner,
+            }
+        }
+    }
+}
+
+fn plain_text(node: &Node) -> String {
+    match node {
+        Node::Text(text) => text.clone(),
+        Node::Element { children, .. } => children.iter().map(plain_text).collect(),
+    }
+}
+
+fn find_element<'a>(node: &'a Node, tag: &str) -> Option<&'a Node> {
+    match node {
+        Node::Text(_) => None,
+        Node::Element { name, children, .. } => {
+            if name == tag {
+                Some(node)
+            } else {
+                children.iter().find_map(|child| find_element(child, tag))
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parse HTML into a tree below a synthetic root element.
+/// This is not a spec-compliant parser, it only needs to handle the markup of the Advent of Code website.
+fn parse(html: &str) -> Node {
+    // stack of open elements, the first entry is the root.
+    let mut stack: Vec<Node> = vec![Node::Element {
+        name: String::new(),
+        class: None,
+        href: None,
+        children: vec![],
+    }];
+
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_child(&mut stack, Node::Text(decode_entities(rest)));
+            break;
+        };
+
+        if tag_start > 0 {
+            push_child(&mut stack, Node::Text(decode_entities(&rest[..tag_start])));
+        }
+        rest = &rest[tag_start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            close_element(&mut stack, &name.trim().to_lowercase());
+            continue;
+        }
+
+        let (name, attrs) = tag
+            .trim_end_matches('/')
+            .split_once(char::is_whitespace)
+            .unwrap_or((tag.trim_end_matches('/'), ""));
+        let name = name.to_lowercase();
+
+        // skip the contents of elements that never contain puzzle text.
+        if name == "script" || name == "style" {
+            let closing = format!("</{name}>");
+            rest = rest
+                .find(&closing)
+                .map_or("", |end| &rest[end + closing.len()..]);
+            continue;
+        }
+
+        let element = Node::Element {
+            class: attribute(attrs, "class"),
+            href: attribute(attrs, "href"),
+            name: name.clone(),
+            children: vec![],
+        };
+
+        if tag.ends_with('/') || VOID_ELEMENTS.contains(&name.as_str()) {
+            push_child(&mut stack, element);
+        } else {
+            stack.push(element);
+        }
+    }
+
+    while stack.len() > 1 {
+        let node = stack.pop().unwrap();
+        push_child(&mut stack, node);
+    }
+
+    stack.pop().unwrap()
+}
+
+fn push_child(stack: &mut [Node], child: Node) {
+    if let Some(Node::Element { children, .. }) = stack.last_mut() {
+        children.push(child);
+    }
+}
+
+/// Close the innermost open element with the given name, implicitly closing any elements opened after it.
+fn close_element(stack: &mut Vec<Node>, tag: &str) {
+    let is_open = stack
+        .iter()
+        .skip(1)
+        .any(|node| matches!(node, Node::Element { name, .. } if name == tag));
+
+    if !is_open {
+        return;
+    }
+
+    while stack.len() > 1 {
+        let node = stack.pop().unwrap();
+        let is_match = matches!(&node, Node::Element { name, .. } if name == tag);
+        push_child(stack, node);
+        if is_match {
+            break;
+        }
+    }
+}
+
+fn attribute(attrs: &str, key: &str) -> Option<String> {
+    let mut rest = attrs;
+
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].split_whitespace().last().unwrap_or_default();
+        let after = rest[eq + 1..].trim_start();
+
+        let (value, remaining) = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = after[1..].find(quote).map_or(after.len(), |e| e + 1);
+                (&after[1..end], after.get(end + 1..).unwrap_or_default())
+            }
+            _ => {
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            }
+        };
+
+        if name.eq_ignore_ascii_case(key) {
+            return Some(decode_entities(value));
+        }
+        rest = remaining;
+    }
+
+    None
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        e if e.starts_with("#x") || e.starts_with("#X") => u32::from_str_radix(&e[2..], 16)
+            .ok()
+            .and_then(char::from_u32),
+        e if e.starts_with('#') => e[1..].parse().ok().and_then(char::from_u32),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, puzzle_title, puzzle_to_markdown};
+
+    const PUZZLE_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><title>Day 1 - Advent of Code 2024</title><script>var x = "<main>";</script></head>
+<body>
+<header><h1 class="title-global"><a href="/">Advent of Code</a></h1></header>
+<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is always present.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul>
+<li>The smallest number in the left list is <code>1</code>.</li>
+<li>A distance of <code>2</code>.</li>
+</ul>
+<p>The total distance is <code><em>11</em></code>. Is <code>a &lt; b</code>?</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>See <a href="/2024/day/1/input" target="_blank">your input</a>.</p>
+</article>
+<p>Both parts of this puzzle are complete! They provide two gold stars: **</p>
+</main>
+</body>
+</html>"#;
+
+    #[test]
+    fn converts_puzzle_html() {
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* is always present.",
+            "",
+            "For example:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "- The smallest number in the left list is `1`.",
+            "- A distance of `2`.",
+            "",
+            "The total distance is *`11`*. Is `a < b`?",
+            "",
+            "Your puzzle answer was `1234`.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "See [your input](/2024/day/1/input).",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(puzzle_to_markdown(PUZZLE_HTML), expected);
+    }
+
+    #[test]
+    fn reads_titles() {
+        let markdown = puzzle_to_markdown(PUZZLE_HTML);
+        assert_eq!(
+            puzzle_title(&markdown),
+            Some("Day 1: Historian Hysteria".into())
+        );
+        assert_eq!(puzzle_title("404"), None);
+    }
+
+    #[test]
+    fn handles_pages_without_puzzles() {
+        assert_eq!(puzzle_to_markdown("<main><p>404</p></main>"), "\n");
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(decode_entities("&lt;&gt;&amp;&quot;&#39;&#x41;"), "<>&\"'A");
+        assert_eq!(decode_entities("a & b;"), "a & b;");
+    }
+}
@@ -0,0 +1,545 @@
+/// Client for the Advent of Code website, used to download inputs, read puzzles and submit answers.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::PuzzleId;
+
+mod markdown;
+
+pub use markdown::{puzzle_title, puzzle_to_markdown};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in `AOC_SESSION` or the session file.
+    MissingSession,
+    /// The server rejected the session cookie.
+    Unauthorized,
+    /// The puzzle does not exist or is not unlocked yet.
+    PuzzleNotFound(PuzzleId),
+    /// The server responded with an unexpected status code.
+    BadStatus(u16),
+    /// The request could not be sent or the response could not be received.
+    Transport(String),
+    /// The response did not have the expected shape.
+    UnexpectedResponse(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or write it to \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it may have expired.")
+            }
+            AocClientError::PuzzleNotFound(puzzle) => {
+                write!(
+                    f,
+                    "the puzzle for day {} of {} is not available (yet).",
+                    puzzle.day, puzzle.year
+                )
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::UnexpectedResponse(e) => write!(f, "unexpected response: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// A hint the server gives about a wrong numeric answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    /// The answer is wrong. The server may tell whether it is too high or too low, and imposes a cooldown.
+    Incorrect {
+        hint: Option<AnswerHint>,
+        wait_seconds: Option<u64>,
+    },
+    /// An answer was submitted too recently, nothing was checked.
+    RateLimited {
+        wait_seconds: u64,
+    },
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmissionOutcome::Incorrect { hint, wait_seconds } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(AnswerHint::TooHigh) => write!(f, ", your answer is too high.")?,
+                    Some(AnswerHint::TooLow) => write!(f, ", your answer is too low.")?,
+                    None => write!(f, ".")?,
+                }
+                if let Some(wait_seconds) = wait_seconds {
+                    write!(f, " Please wait {wait_seconds}s before trying again.")?;
+                }
+                Ok(())
+            }
+            SubmissionOutcome::RateLimited { wait_seconds } => write!(
+                f,
+                "You gave an answer too recently, {wait_seconds}s left to wait."
+            ),
+            SubmissionOutcome::WrongLevel => write!(
+                f,
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+        }
+    }
+}
+
+/// A client for the Advent of Code website.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    /// Create a client configured by the environment:
+    ///  1. the session cookie is read from `AOC_SESSION` or the session file in the home or config directory.
+    ///  2. the base url can be overridden with `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(read_session_file)
+            .filter(|s| !s.trim().is_empty())
+            .ok_or(AocClientError::MissingSession)?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetch the personal puzzle input for a puzzle.
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(
+            &format!("{}/day/{}/input", puzzle.year, puzzle.day.into_inner()),
+            puzzle,
+        )
+    }
+
+    /// Fetch the HTML page of a puzzle.
+    pub fn get_puzzle_html(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(
+            &format!("{}/day/{}", puzzle.year, puzzle.day.into_inner()),
+            puzzle,
+        )
+    }
+
+    /// Fetch a puzzle and convert its description to markdown.
+    pub fn get_puzzle_markdown(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get_puzzle_html(puzzle)
+            .map(|html| puzzle_to_markdown(&html))
+    }
+
+    /// Submit an answer for one part of a puzzle.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        );
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| map_error(e, puzzle))?;
+
+        parse_submission(&response.into_string()?)
+    }
+
+    fn get(&self, path: &str, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/{path}", self.base_url);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| map_error(e, puzzle))?;
+
+        Ok(response.into_string()?)
+    }
+}
+
+fn map_error(e: ureq::Error, puzzle: PuzzleId) -> AocClientError {
+    match e {
+        // the server responds with 400 to input requests without a valid session.
+        ureq::Error::Status(400 | 401 | 403, _) => AocClientError::Unauthorized,
+        ureq::Error::Status(404, _) => AocClientError::PuzzleNotFound(puzzle),
+        ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+        ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+    }
+}
+
+/// Interpret the HTML returned after submitting an answer.
+pub fn parse_submission(html: &str) -> Result<SubmissionOutcome, AocClientError> {
+    let markdown = article_text(html);
+
+    if markdown.contains("That's the right answer") {
+        Ok(SubmissionOutcome::Correct)
+    } else if markdown.contains("That's not the right answer") {
+        let hint = if markdown.contains("too high") {
+            Some(AnswerHint::TooHigh)
+        } else if markdown.contains("too low") {
+            Some(AnswerHint::TooLow)
+        } else {
+            None
+        };
+
+        Ok(SubmissionOutcome::Incorrect {
+            hint,
+            wait_seconds: parse_wait_seconds(&markdown),
+        })
+    } else if markdown.contains("You gave an answer too recently") {
+        Ok(SubmissionOutcome::RateLimited {
+            wait_seconds: parse_wait_seconds(&markdown).unwrap_or(60),
+        })
+    } else if markdown.contains("You don't seem to be solving the right level") {
+        Ok(SubmissionOutcome::WrongLevel)
+    } else {
+        Err(AocClientError::UnexpectedResponse(
+            "could not interpret submission response.".into(),
+        ))
+    }
+}
+
+/// Responses to submissions are a single `<article>` without the `day-desc` class, extract its text.
+fn article_text(html: &str) -> String {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |end| start + end);
+    let article = &html[start..end];
+    // strip tags, the messages are plain text.
+    let mut text = String::with_capacity(article.len());
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&#39;", "'").replace("&apos;", "'")
+}
+
+/// Parse cooldowns such as "You have 1m 5s left to wait" or "please wait one minute before trying again".
+fn parse_wait_seconds(text: &str) -> Option<u64> {
+    if let Some(start) = text.find("You have ") {
+        let rest = &text[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+        let seconds = rest[..end]
+            .split_whitespace()
+            .map(|part| {
+                let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+        return Some(seconds);
+    }
+
+    let start = text.find("wait ")?;
+    let rest = &text[start + "wait ".len()..];
+    let mut words = rest.split_whitespace();
+    let amount = match words.next()? {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(amount * 60),
+        unit if unit.starts_with("second") => Some(amount),
+        _ => None,
+    }
+}
+
+fn read_session_file() -> Option<String> {
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .ok()
+        .map(PathBuf::from);
+
+    let config = env::var("XDG_CONFIG_HOME")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|p| p.join(SESSION_FILE_NAME)),
+        config.map(|p| p.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|path| fs::read_to_string(path).ok())
+}
+
+/// Download the input and puzzle description of a puzzle to the `data` directory of its year.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = puzzle.input_path();
+    let puzzle_path = puzzle.puzzle_path();
+
+    let description = client.get_puzzle_markdown(puzzle)?;
+    let input = client.get_input(puzzle)?;
+
+    create_parent_dir(&puzzle_path)?;
+    create_parent_dir(&input_path)?;
+    fs::write(&puzzle_path, description)?;
+    fs::write(&input_path, input)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetch the description of a puzzle, store it and print it to the terminal.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    println!("Fetching puzzle for day {}, {}...", puzzle.day, puzzle.year);
+
+    let description = client.get_puzzle_markdown(puzzle)?;
+    let puzzle_path = puzzle.puzzle_path();
+    create_parent_dir(&puzzle_path)?;
+    fs::write(puzzle_path, &description)?;
+
+    println!();
+    println!("{description}");
+    Ok(())
+}
+
+/// Submit an answer for one part of a puzzle.
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    answer: &str,
+) -> Result<SubmissionOutcome, AocClientError> {
+    AocClient::from_env()?.submit(puzzle, part, answer)
+}
+
+fn create_parent_dir(path: &str) -> io::Result<()> {
+    match Path::new(path).parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use super::{
+        parse_submission, parse_wait_seconds, AnswerHint, AocClient, AocClientError,
+        SubmissionOutcome,
+    };
+    use crate::puzzle;
+
+    /// Serve a single request with a canned response, returns the base url and a receiver for the raw request.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body_buf = vec![0; content_length];
+            reader.read_exact(&mut body_buf).unwrap();
+            request.push_str(&String::from_utf8_lossy(&body_buf));
+            tx.send(request).unwrap();
+
+            let response = format!(
+                "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+        });
+
+        (format!("http://{addr}"), rx)
+    }
+
+    #[test]
+    fn fetches_inputs() {
+        let (url, rx) = mock_server(200, "3   4\n4   3\n");
+        let client = AocClient::new(&url, "secret\n");
+        assert_eq!(
+            client.get_input(puzzle!(2024, 1)).unwrap(),
+            "3   4\n4   3\n"
+        );
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn fetches_puzzles_as_markdown() {
+        let (url, _rx) = mock_server(
+            200,
+            r#"<main><article class="day-desc"><h2>--- Day 2: Red-Nosed Reports ---</h2></article></main>"#,
+        );
+        let client = AocClient::new(&url, "secret");
+        assert_eq!(
+            client.get_puzzle_markdown(puzzle!(2024, 2)).unwrap(),
+            "## --- Day 2: Red-Nosed Reports ---\n"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, rx) = mock_server(
+            200,
+            "<main><article><p>That's the right answer!  You are one gold star closer.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+        assert_eq!(
+            client.submit(puzzle!(2024, 3), 2, "42").unwrap(),
+            SubmissionOutcome::Correct
+        );
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_typed_errors() {
+        let (url, _rx) = mock_server(400, "Puzzle inputs differ by user.  Please log in.");
+        let client = AocClient::new(&url, "expired");
+        assert!(matches!(
+            client.get_input(puzzle!(2024, 1)),
+            Err(AocClientError::Unauthorized)
+        ));
+
+        let (url, _rx) = mock_server(404, "Not Found");
+        let client = AocClient::new(&url, "secret");
+        assert!(matches!(
+            client.get_puzzle_html(puzzle!(2024, 25)),
+            Err(AocClientError::PuzzleNotFound(d)) if d == puzzle!(2024, 25)
+        ));
+
+        let (url, _rx) = mock_server(500, "");
+        let client = AocClient::new(&url, "secret");
+        assert!(matches!(
+            client.get_input(puzzle!(2024, 1)),
+            Err(AocClientError::BadStatus(500))
+        ));
+    }
+
+    #[test]
+    fn parses_submission_responses() {
+        assert_eq!(
+            parse_submission("<article><p>That&#39;s not the right answer; your answer is too high.  If you&#39;re stuck, make sure you&#39;re using the full input data; please wait one minute before trying again. [<a href=\"/2024/day/1\">Return to Day 1</a>]</p></article>").unwrap(),
+            SubmissionOutcome::Incorrect { hint: Some(AnswerHint::TooHigh), wait_seconds: Some(60) }
+        );
+        assert_eq!(
+            parse_submission("<article><p>That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.</p></article>").unwrap(),
+            SubmissionOutcome::Incorrect { hint: Some(AnswerHint::TooLow), wait_seconds: Some(300) }
+        );
+        assert_eq!(
+            parse_submission("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>").unwrap(),
+            SubmissionOutcome::RateLimited { wait_seconds: 65 }
+        );
+        assert_eq!(
+            parse_submission("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>").unwrap(),
+            SubmissionOutcome::WrongLevel
+        );
+        assert!(parse_submission("<html></html>").is_err());
+        assert!(parse_submission("</article><article><p>Unclosed").is_err());
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(parse_wait_seconds("You have 32s left to wait."), Some(32));
+        assert_eq!(
+            parse_wait_seconds("You have 2m 0s left to wait."),
+            Some(120)
+        );
+        assert_eq!(parse_wait_seconds("no cooldown"), None);
+    }
+}
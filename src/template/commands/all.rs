@@ -1,5 +1,20 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::{all_days, Error, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(year: Year, options: &RunOptions) -> Result<(), Error> {
+    let summary = run_multi(year, &all_days().collect(), options)?;
+
+    // a changed answer fails the run, so that refactorings can be verified in scripts.
+    summary.print_wrong_answers();
+    summary.print_failures();
+
+    if !summary.failures.is_empty() {
+        return Err(Error::SolutionsFailed(summary.failures.len()));
+    }
+
+    if !summary.wrong_answers.is_empty() {
+        return Err(Error::WrongAnswers(summary.wrong_answers.len()));
+    }
+
+    Ok(())
 }
@@ -0,0 +1,17 @@
+use crate::template::{answers, Error, PuzzleId};
+
+pub fn handle(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), Error> {
+    if !(1..=2).contains(&part) {
+        return Err(Error::Usage(format!("part must be 1 or 2, got {part}.")));
+    }
+
+    let path = answers::get_answer_path(puzzle, part);
+    answers::store(puzzle, part, answer)
+        .map_err(|e| Error::io(format!("could not store answer in \"{path}\""), e))?;
+
+    println!(
+        "Stored answer for day {} part {part} in \"{path}\".",
+        puzzle.day
+    );
+    Ok(())
+}
@@ -0,0 +1,480 @@
+/// Compares the timings of the solutions in the working tree against a git revision, see `cargo time --compare <rev>`.
+use std::{
+    env,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+use crate::template::output::{part_label, PartResult, PARSE_PART};
+use crate::template::stats::{median, nanos_to_duration, Speedup};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{
+    all_days, Day, Error, PuzzleId, Year, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+};
+
+/// Number of rounds in which both revisions are benched, see `--rounds`.
+pub const DEFAULT_ROUNDS: usize = 5;
+
+/// The target directory of the compared revision. It is kept between runs so that comparisons against the same revision build incrementally.
+const COMPARE_TARGET_DIR: &str = "./target/compare";
+
+pub fn handle(year: Year, day: Option<Day>, rev: &str, rounds: usize) -> Result<(), Error> {
+    if rounds < 2 {
+        return Err(Error::Usage(
+            "`--rounds` must be at least 2 to estimate the spread of the speedup.".into(),
+        ));
+    }
+
+    let worktree = Worktree::add(rev)?;
+
+    let baseline = Checkout {
+        name: rev.to_string(),
+        manifest_path: worktree.path.join("Cargo.toml"),
+        target_dir: Some(PathBuf::from(COMPARE_TARGET_DIR)),
+    };
+    let candidate = Checkout {
+        name: String::from("the working tree"),
+        manifest_path: PathBuf::from("Cargo.toml"),
+        target_dir: None,
+    };
+
+    let solved: Vec<PuzzleId> = day
+        .map_or_else(|| all_days().collect(), |day| vec![day])
+        .into_iter()
+        .map(|day| PuzzleId::new(year, day))
+        .filter(|puzzle| Path::new(&puzzle.bin_path()).exists())
+        .collect();
+
+    // only days that are solved in both the working tree and the revision can be compared.
+    let puzzles: Vec<PuzzleId> = solved
+        .iter()
+        .copied()
+        .filter(|puzzle| worktree.path.join(puzzle.bin_path()).exists())
+        .collect();
+
+    if puzzles.is_empty() {
+        if !solved.is_empty() && worktree.has_single_year_layout() {
+            return Err(Error::Usage(format!(
+                "{rev} keeps its solutions in `src/bin/<day>.rs`, which can't be compared with `src/bin/<year>_<day>.rs`."
+            )));
+        }
+
+        if let Some(day) = day.filter(|_| !solved.is_empty()) {
+            eprintln!("Day {day} is not solved in {rev}.");
+        }
+
+        println!("No days to compare.");
+        return Ok(());
+    }
+
+    println!("Building {rev}...");
+    baseline.build(&puzzles)?;
+    println!("Building the working tree...");
+    candidate.build(&puzzles)?;
+
+    let mut baseline_rounds: Vec<Timings> = vec![];
+    let mut candidate_rounds: Vec<Timings> = vec![];
+    let mut answers_differ: Vec<PuzzleId> = vec![];
+
+    for round in 0..rounds {
+        println!("Round {}/{rounds}...", round + 1);
+
+        let mut baseline_timings = Timings::default();
+        let mut candidate_timings = Timings::default();
+
+        for puzzle in &puzzles {
+            // alternate which revision runs first, so that neither profits from a warmed-up machine.
+            let (baseline_results, candidate_results) = if round % 2 == 0 {
+                let baseline_results = baseline.run(*puzzle)?;
+                (baseline_results, candidate.run(*puzzle)?)
+            } else {
+                let candidate_results = candidate.run(*puzzle)?;
+                (baseline.run(*puzzle)?, candidate_results)
+            };
+
+            if round == 0 && !same_answers(&baseline_results, &candidate_results) {
+                answers_differ.push(*puzzle);
+            }
+
+            baseline_timings
+                .data
+                .push(Timing::from_results(puzzle.day, &baseline_results));
+            candidate_timings
+                .data
+                .push(Timing::from_results(puzzle.day, &candidate_results));
+        }
+
+        baseline_rounds.push(baseline_timings);
+        candidate_rounds.push(candidate_timings);
+    }
+
+    println!();
+    for puzzle in &puzzles {
+        print_comparison(puzzle.day, rev, &baseline_rounds, &candidate_rounds);
+    }
+
+    for puzzle in answers_differ {
+        eprintln!(
+            "{ANSI_RED}Day {}: the answers of {rev} and the working tree differ.{ANSI_RESET}",
+            puzzle.day
+        );
+    }
+
+    Ok(())
+}
+
+fn same_answers(baseline: &[PartResult], candidate: &[PartResult]) -> bool {
+    let answers = |results: &[PartResult]| -> Vec<(u8, Option<String>)> {
+        results
+            .iter()
+            .map(|result| (result.part, result.answer.clone()))
+            .collect()
+    };
+    answers(baseline) == answers(candidate)
+}
+
+/// Print the median duration of each part in both revisions and the speedup of the working tree.
+fn print_comparison(day: Day, rev: &str, baseline: &[Timings], candidate: &[Timings]) {
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+    println!(
+        "{:<8} {:>12} {:>12}   speedup (95% CI)",
+        "",
+        truncate(rev, 12),
+        "working tree"
+    );
+
+    for part in [PARSE_PART, 1, 2] {
+        if let Some(row) = comparison_row(day, part, baseline, candidate) {
+            println!("{row}");
+        }
+    }
+
+    println!();
+}
+
+/// Format the comparison of a part across all rounds, or `None` if the part was not measured at all.
+fn comparison_row(
+    day: Day,
+    part: u8,
+    baseline: &[Timings],
+    candidate: &[Timings],
+) -> Option<String> {
+    let part_nanos = |timings: &Timings| -> Option<f64> {
+        timings
+            .data
+            .iter()
+            .find(|t| t.day == day)
+            .and_then(|timing| timing.part_nanos(part))
+    };
+
+    let rounds: Vec<(Option<f64>, Option<f64>)> = baseline
+        .iter()
+        .zip(candidate)
+        .map(|(baseline, candidate)| (part_nanos(baseline), part_nanos(candidate)))
+        .collect();
+
+    if rounds.iter().all(|round| *round == (None, None)) {
+        return None;
+    }
+
+    // a part that failed in any round can't be paired.
+    let Some((baseline_nanos, candidate_nanos)): Option<(Vec<f64>, Vec<f64>)> = rounds
+        .into_iter()
+        .map(|(baseline, candidate)| baseline.zip(candidate))
+        .collect::<Option<Vec<(f64, f64)>>>()
+        .map(|pairs| pairs.into_iter().unzip())
+    else {
+        return Some(format!(
+            "{:<8} could not be measured in every round.",
+            part_label(part)
+        ));
+    };
+
+    let speedup = Speedup::from_pairs(&baseline_nanos, &candidate_nanos)?;
+
+    let color = if speedup.is_faster() {
+        ANSI_GREEN
+    } else if speedup.is_slower() {
+        ANSI_RED
+    } else {
+        ""
+    };
+
+    Some(format!(
+        "{:<8} {:>12} {:>12}   {color}{speedup}{ANSI_RESET}",
+        part_label(part),
+        format!("{:.1?}", nanos_to_duration(median(&baseline_nanos))),
+        format!("{:.1?}", nanos_to_duration(median(&candidate_nanos))),
+    ))
+}
+
+fn truncate(s: &str, len: usize) -> &str {
+    s.char_indices().nth(len).map_or(s, |(ix, _)| &s[..ix])
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A source tree whose solutions are built and run with cargo.
+/// Solutions always run in the current directory, so that both revisions read the same inputs.
+struct Checkout {
+    /// The revision or tree the checkout is referred to by in messages.
+    name: String,
+    manifest_path: PathBuf,
+    target_dir: Option<PathBuf>,
+}
+
+impl Checkout {
+    fn cargo(&self, subcommand: &str) -> Command {
+        let mut cmd = Command::new("cargo");
+        cmd.args([subcommand, "--release", "--quiet", "--manifest-path"])
+            .arg(&self.manifest_path);
+        if let Some(target_dir) = &self.target_dir {
+            cmd.arg("--target-dir").arg(target_dir);
+        }
+        cmd
+    }
+
+    fn build(&self, puzzles: &[PuzzleId]) -> Result<(), Error> {
+        let mut cmd = self.cargo("build");
+        for puzzle in puzzles {
+            cmd.args(["--bin", &puzzle.bin_name()]);
+        }
+
+        let command = format!("{cmd:?}");
+        let status = cmd.status().map_err(|source| Error::CargoSpawn {
+            command: command.clone(),
+            source,
+        })?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::CargoFailed { command, status })
+        }
+    }
+
+    /// Bench a solution once, returning the result of each part.
+    fn run(&self, puzzle: PuzzleId) -> Result<Vec<PartResult>, Error> {
+        let mut cmd = self.cargo("run");
+        cmd.args([
+            "--bin",
+            &puzzle.bin_name(),
+            "--",
+            "--format",
+            "json",
+            "--time",
+        ])
+        .stderr(Stdio::inherit());
+
+        let command = format!("{cmd:?}");
+        let output = cmd.output().map_err(|source| Error::CargoSpawn {
+            command: command.clone(),
+            source,
+        })?;
+
+        if !output.status.success() {
+            return Err(Error::CargoFailed {
+                command,
+                status: output.status,
+            });
+        }
+
+        let results: Vec<PartResult> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.parse::<PartResult>().ok())
+            .collect();
+
+        // revisions from before `--format json` print their results as text, which can't be compared.
+        if results.is_empty() {
+            return Err(Error::Usage(format!(
+                "{} does not support `--format json`.",
+                self.name
+            )));
+        }
+
+        Ok(results)
+    }
+}
+
+/// A temporary git worktree, which is removed when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn add(rev: &str) -> Result<Self, Error> {
+        // fail early for unknown revisions, before anything is created.
+        git(&[
+            OsStr::new("rev-parse"),
+            OsStr::new("--verify"),
+            OsStr::new(&format!("{rev}^{{commit}}")),
+        ])?;
+
+        let path = env::temp_dir().join(format!("advent_of_code-compare-{}", process::id()));
+
+        git(&[
+            OsStr::new("worktree"),
+            OsStr::new("add"),
+            OsStr::new("--detach"),
+            path.as_os_str(),
+            OsStr::new(rev),
+        ])?;
+
+        Ok(Worktree { path })
+    }
+
+    /// Whether the revision predates the `<year>_<day>` naming of solutions, i.e. has solutions named `<day>.rs`.
+    fn has_single_year_layout(&self) -> bool {
+        fs::read_dir(self.path.join("src/bin"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .any(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                name.len() == 5
+                    && name.ends_with(".rs")
+                    && name[..2].chars().all(|c| c.is_ascii_digit())
+            })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(e) = git(&[
+            OsStr::new("worktree"),
+            OsStr::new("remove"),
+            OsStr::new("--force"),
+            self.path.as_os_str(),
+        ]) {
+            eprintln!("Failed to remove temporary worktree: {e}");
+        }
+    }
+}
+
+fn git(args: &[&OsStr]) -> Result<String, Error> {
+    let command = format!(
+        "git {}",
+        args.iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| Error::Git {
+            command: command.clone(),
+            reason: e.to_string(),
+        })?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(Error::Git {
+            command,
+            reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{comparison_row, same_answers};
+    use crate::{
+        day,
+        template::{
+            answers::AnswerCheck,
+            output::{PartResult, PartStatus},
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn result(part: u8, answer: Option<&str>, duration_nanos: f64) -> PartResult {
+        PartResult {
+            day: day!(1),
+            part,
+            answer: answer.map(Into::into),
+            duration_nanos,
+            samples: 10,
+            stats: None,
+            alloc: None,
+            check: AnswerCheck::Unknown,
+            status: PartStatus::Finished,
+        }
+    }
+
+    fn round(results: &[PartResult]) -> Timings {
+        Timings {
+            data: vec![Timing::from_results(day!(1), results)],
+        }
+    }
+
+    #[test]
+    fn compares_answers() {
+        let baseline = [result(1, Some("42"), 100.0), result(2, None, 0.0)];
+
+        assert!(same_answers(
+            &baseline,
+            &[result(1, Some("42"), 50.0), result(2, None, 0.0)]
+        ));
+        assert!(!same_answers(
+            &baseline,
+            &[result(1, Some("41"), 100.0), result(2, None, 0.0)]
+        ));
+        assert!(!same_answers(&baseline, &[result(1, Some("42"), 100.0)]));
+    }
+
+    #[test]
+    fn compares_part_across_rounds() {
+        let baseline = [
+            round(&[result(1, Some("42"), 200.0)]),
+            round(&[result(1, Some("42"), 200.0)]),
+        ];
+        let candidate = [
+            round(&[result(1, Some("42"), 100.0)]),
+            round(&[result(1, Some("42"), 100.0)]),
+        ];
+
+        let row = comparison_row(day!(1), 1, &baseline, &candidate).unwrap();
+        assert!(row.starts_with("Part 1"));
+        assert!(row.contains("2.00x [2.00x, 2.00x]"));
+
+        assert_eq!(comparison_row(day!(1), 2, &baseline, &candidate), None);
+    }
+
+    #[test]
+    fn reports_part_missing_from_a_round() {
+        let baseline = [
+            round(&[result(1, Some("42"), 200.0)]),
+            round(&[result(1, Some("42"), 200.0)]),
+        ];
+        let candidate = [
+            round(&[result(1, Some("42"), 100.0)]),
+            round(&[PartResult::failed(day!(1), 1, PartStatus::Crashed, 0.0)]),
+        ];
+
+        assert_eq!(
+            comparison_row(day!(1), 1, &baseline, &candidate).as_deref(),
+            Some("Part 1   could not be measured in every round.")
+        );
+    }
+
+    #[test]
+    fn pairs_parts_by_round() {
+        let crashed = || round(&[PartResult::failed(day!(1), 1, PartStatus::Crashed, 0.0)]);
+        let baseline = [crashed(), round(&[result(1, Some("42"), 200.0)])];
+        let candidate = [round(&[result(1, Some("42"), 100.0)]), crashed()];
+
+        assert_eq!(
+            comparison_row(day!(1), 1, &baseline, &candidate).as_deref(),
+            Some("Part 1   could not be measured in every round.")
+        );
+    }
+}
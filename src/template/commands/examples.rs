@@ -0,0 +1,61 @@
+use std::fs;
+
+use crate::template::{examples, Error, PuzzleId};
+
+/// Write the examples found in the puzzle description to the examples folder and fill them into the tests of the day.
+/// Existing example files are only replaced if they are empty or `overwrite` is set.
+/// Answers are filled in as values of `answer_type`, the return type the day was scaffolded with.
+pub fn handle(puzzle: PuzzleId, overwrite: bool, answer_type: &str) -> Result<(), Error> {
+    let puzzle_path = puzzle.puzzle_path();
+    let markdown = fs::read_to_string(&puzzle_path).map_err(|e| {
+        Error::io(
+            format!(
+                "could not read puzzle description \"{puzzle_path}\", run `cargo read {}` first",
+                puzzle.day
+            ),
+            e,
+        )
+    })?;
+
+    let examples = examples::extract(&markdown);
+    if examples.iter().all(|example| example.input.is_none()) {
+        println!("No examples found in \"{puzzle_path}\".");
+        return Ok(());
+    }
+
+    for (part, example) in (1..).zip(&examples) {
+        let Some(input) = &example.input else {
+            continue;
+        };
+
+        let path = if part == 1 {
+            puzzle.example_path()
+        } else {
+            puzzle.data_part_path("examples", part)
+        };
+
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+        if !is_empty && !overwrite {
+            println!("Keeping existing example file \"{path}\".");
+            continue;
+        }
+
+        fs::write(&path, input)
+            .map_err(|e| Error::io(format!("could not write example file \"{path}\""), e))?;
+        println!("Wrote example to \"{path}\"");
+    }
+
+    let module_path = puzzle.bin_path();
+    let Ok(module) = fs::read_to_string(&module_path) else {
+        return Ok(());
+    };
+
+    let filled = examples::fill_tests(&module, &examples, answer_type);
+    if filled != module {
+        fs::write(&module_path, filled)
+            .map_err(|e| Error::io(format!("could not write module file \"{module_path}\""), e))?;
+        println!("Filled expected answers into \"{module_path}\"");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use crate::template::timings::Timings;
+use crate::template::{readme, Error, TableOptions, Year};
+
+/// Rewrite the stars and benchmark tables of the readme from local data, without running any solution.
+pub fn handle(year: Year, options: &TableOptions) -> Result<(), Error> {
+    let timings = Timings::read_from_file(year)?;
+    readme::update(year, timings, options)?;
+    println!("Updated the readme.");
+    Ok(())
+}
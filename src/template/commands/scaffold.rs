@@ -1,13 +1,125 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::{aoc_client, Error, PuzzleId};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Directory of the templates that can be selected with `--template <name>`, one `<name>.txt` file per template.
+pub const TEMPLATES_DIR: &str = "./src/templates";
+pub const DEFAULT_TEMPLATE: &str = "default";
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Values substituted for the `%PLACEHOLDER%`s of a template.
+struct Placeholders {
+    /// `%YEAR%`, e.g. `2024`.
+    year: String,
+    /// `%DAY_NUMBER%`, e.g. `5`.
+    day_number: String,
+    /// `%DAY%`, e.g. `05`.
+    day: String,
+    /// `%TITLE%`, e.g. `Day 5: Print Queue`, or `Day 05` if the puzzle was not downloaded yet.
+    title: String,
+    /// `%ANSWER_TYPE%`, the return type of the parts wrapped in an `Option`.
+    answer_type: String,
+    /// `%EXAMPLE_1%` and `%EXAMPLE_2%`, the call that reads the example of a part.
+    examples: [String; 2],
+}
+
+impl Placeholders {
+    fn new(puzzle: PuzzleId, answer_type: &str) -> Self {
+        let title = fs::read_to_string(puzzle.puzzle_path())
+            .ok()
+            .and_then(|markdown| aoc_client::puzzle_title(&markdown))
+            .unwrap_or_else(|| format!("Day {}", puzzle.day));
+
+        // parts with their own example file read it, all others read the shared example.
+        let examples = [1, 2].map(|part| {
+            if Path::new(&puzzle.data_part_path("examples", part)).exists() {
+                format!("read_file_part(\"examples\", PUZZLE, {part})")
+            } else {
+                "read_file(\"examples\", PUZZLE)".to_string()
+            }
+        });
+
+        Placeholders {
+            year: puzzle.year.to_string(),
+            day_number: puzzle.day.into_inner().to_string(),
+            day: puzzle.day.to_string(),
+            title,
+            answer_type: answer_type.to_string(),
+            examples,
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "YEAR" => Some(&self.year),
+            "DAY_NUMBER" => Some(&self.day_number),
+            "DAY" => Some(&self.day),
+            "TITLE" => Some(&self.title),
+            "ANSWER_TYPE" => Some(&self.answer_type),
+            "EXAMPLE_1" => Some(&self.examples[0]),
+            "EXAMPLE_2" => Some(&self.examples[1]),
+            _ => None,
+        }
+    }
+}
+
+/// Replace all placeholders of a template. Fails on placeholders that are not known.
+fn render(template: &str, placeholders: &Placeholders) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('%') {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let name = rest.find('%').map(|end| &rest[..end]).filter(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        });
+
+        match name {
+            Some(name) => {
+                let value = placeholders
+                    .get(name)
+                    .ok_or_else(|| format!("unknown placeholder `%{name}%`"))?;
+                rendered.push_str(value);
+                rest = &rest[name.len() + 1..];
+            }
+            None => rendered.push('%'),
+        }
+    }
+
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+fn read_template(name: &str) -> Result<String, String> {
+    let path = format!("{TEMPLATES_DIR}/{name}.txt");
+    fs::read_to_string(&path).map_err(|e| {
+        let mut available: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                (path.extension()? == "txt")
+                    .then(|| path.file_stem()?.to_str().map(String::from))
+                    .flatten()
+            })
+            .collect();
+        available.sort();
+        format!(
+            "could not read template \"{path}\": {e}. Available templates: {}",
+            available.join(", ")
+        )
+    })
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,61 +131,104 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
-}
-
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
-
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// Create an empty data file, keeping a file that already exists, e.g. a downloaded input.
+fn create_data_file(path: &str, kind: &str) -> Result<(), Error> {
+    match safe_create_file(path, false) {
+        Ok(_) => {
+            println!("Created empty {kind} file \"{path}\"");
         }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Keeping existing {kind} file \"{path}\"");
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+            return Err(Error::io(
+                format!("could not create {kind} file \"{path}\""),
+                e,
+            ));
         }
     }
+    Ok(())
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+pub fn handle(
+    puzzle: PuzzleId,
+    overwrite: bool,
+    template: &str,
+    answer_type: &str,
+) -> Result<(), Error> {
+    let input_path = puzzle.input_path();
+    let example_path = puzzle.example_path();
+    let module_path = puzzle.bin_path();
+
+    for folder in ["inputs", "examples", "puzzles"] {
+        let path = format!("{}/{folder}", puzzle.data_dir());
+        fs::create_dir_all(&path)
+            .map_err(|e| Error::io(format!("could not create data directory \"{path}\""), e))?;
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    let contents = read_template(template)
+        .and_then(|template| render(&template, &Placeholders::new(puzzle, answer_type)))
+        .map_err(|e| Error::Usage(format!("failed to render template `{template}`: {e}")))?;
+
+    safe_create_file(&module_path, overwrite)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|e| Error::io(format!("could not create module file \"{module_path}\""), e))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    create_data_file(&input_path, "input")?;
+    create_data_file(&example_path, "example")?;
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Placeholders};
+
+    fn placeholders() -> Placeholders {
+        Placeholders {
+            year: "2024".into(),
+            day_number: "5".into(),
+            day: "05".into(),
+            title: "Day 5: Print Queue".into(),
+            answer_type: "u64".into(),
+            examples: [
+                "read_file(\"examples\", PUZZLE)".into(),
+                "read_file_part(\"examples\", PUZZLE, 2)".into(),
+            ],
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    #[test]
+    fn renders_placeholders() {
+        let template = "//! %TITLE% (%DAY%)\nsolution!(%YEAR%, %DAY_NUMBER%);\nfn part_two() -> Option<%ANSWER_TYPE%> { %EXAMPLE_2% % 2 }";
+        assert_eq!(
+            render(template, &placeholders()).unwrap(),
+            "//! Day 5: Print Queue (05)\nsolution!(2024, 5);\nfn part_two() -> Option<u64> { read_file_part(\"examples\", PUZZLE, 2) % 2 }"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders() {
+        assert_eq!(
+            render("%DAY_NAME%", &placeholders()),
+            Err("unknown placeholder `%DAY_NAME%`".into())
+        );
+    }
+
+    #[test]
+    fn renders_all_templates() {
+        for entry in std::fs::read_dir(super::TEMPLATES_DIR).unwrap() {
+            let template = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            let rendered = render(&template, &placeholders()).unwrap();
+            assert!(rendered.contains("solution!(2024, 5"));
+        }
+    }
 }
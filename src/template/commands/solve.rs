@@ -1,9 +1,43 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{Error, OutputFormat, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// The input passed to the solution binary, see [`crate::template::runner::read_input`].
+#[derive(Clone, Debug, Default)]
+pub struct InputArgs {
+    pub input: Option<String>,
+    pub example: Option<Option<u8>>,
+    pub stdin: bool,
+}
+
+/// The part to submit with `--submit <part>`, see [`crate::template::runner`].
+#[derive(Clone, Copy, Debug)]
+pub struct SubmitArgs {
+    pub part: u8,
+    /// Submit even if the submission ledger can't be read, skipping its checks.
+    pub ignore_ledger: bool,
+}
+
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    alloc: bool,
+    submit: Option<SubmitArgs>,
+    format: OutputFormat,
+    input_args: InputArgs,
+) -> Result<(), Error> {
+    if dhat && alloc {
+        return Err(Error::Usage(
+            "`--dhat` and `--alloc` can't be combined.".into(),
+        ));
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
+
+    if alloc {
+        cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+    }
 
     if dhat {
         cmd_args.extend([
@@ -18,17 +52,48 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if format != OutputFormat::Text {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    if let Some(path) = input_args.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(path);
+    }
+
+    if let Some(part) = input_args.example {
+        cmd_args.push("--example".to_string());
+        cmd_args.extend(part.map(|part| part.to_string()));
+    }
+
+    if input_args.stdin {
+        cmd_args.push("-".to_string());
+    }
+
+    if let Some(submit) = submit {
         cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+        cmd_args.push(submit.part.to_string());
+        if submit.ignore_ledger {
+            cmd_args.push("--ignore-ledger".to_string());
+        }
     }
 
-    let mut cmd = Command::new("cargo")
+    let command = format!("cargo {}", cmd_args.join(" "));
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|source| Error::CargoSpawn {
+            command: command.clone(),
+            source,
+        })?;
 
-    cmd.wait().unwrap();
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::CargoFailed { command, status })
+    }
 }
@@ -1,11 +1,26 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::{History, HistoryEntry, Machine, Regression};
+use crate::template::output::part_label;
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::stats::nanos_to_duration;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, readme, Baseline, Day, Error, OutputFormat, TableOptions, Year, ANSI_BOLD, ANSI_RED,
+    ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Bench the solutions. With `store`, the timings are stored and the readme table is rewritten with the given options.
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: Option<TableOptions>,
+    options: &RunOptions,
+    baseline: Baseline,
+    threshold: f64,
+) -> Result<(), Error> {
+    let stored_timings = Timings::read_from_file(year)?;
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,20 +36,69 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        format: OutputFormat::Text,
+        jobs: 1,
+        ..*options
+    };
 
-    if store {
+    let summary = run_multi(year, &days_to_run, &options)?;
+    summary.print_wrong_answers();
+    summary.print_failures();
+
+    let timings = summary.timings.unwrap_or_default();
+
+    report_regressions(year, &timings, baseline, threshold);
+
+    if let Some(table_options) = store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year)?;
+        readme::update(year, merged_timings, &table_options)?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
+        println!("Stored updated benchmarks.");
+    }
+
+    Ok(())
+}
+
+/// Compare the run against the benchmark history, then append it to the history.
+fn report_regressions(year: Year, timings: &Timings, baseline: Baseline, threshold: f64) {
+    let history = match History::read_from_file(year) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e}");
+            return;
         }
+    };
+
+    let machine = Machine::current();
+    let regressions = history.find_regressions(timings, &machine, baseline, threshold);
+
+    println!();
+    if regressions.is_empty() {
+        println!("No regressions above {threshold}% compared to the {baseline} run.");
+    } else {
+        println!(
+            "{ANSI_BOLD}Regressions above {threshold}% compared to the {baseline} run:{ANSI_RESET}"
+        );
+        regressions.iter().for_each(print_regression);
+    }
+
+    if let Err(e) = History::append_file(year, &HistoryEntry::new(timings.clone())) {
+        eprintln!("Failed to append run to benchmark history: {e}");
     }
 }
+
+fn print_regression(regression: &Regression) {
+    println!(
+        "{ANSI_RED}Day {} {}: {:.1?} -> {:.1?} (+{:.1}%){ANSI_RESET}",
+        regression.day,
+        part_label(regression.part),
+        nanos_to_duration(regression.baseline_nanos),
+        nanos_to_duration(regression.current_nanos),
+        regression.change_pct()
+    );
+}
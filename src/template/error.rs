@@ -0,0 +1,163 @@
+/// The error type of the template layer. Subcommands return it and the main binary reports it with a distinct exit code.
+use std::{fmt::Display, io, process::ExitStatus};
+
+use crate::template::aoc_client::AocClientError;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line arguments or options.
+    Usage(String),
+    /// An input or example file could not be read.
+    MissingInput { path: String, source: io::Error },
+    /// A cargo command could not be started.
+    CargoSpawn { command: String, source: io::Error },
+    /// A cargo command, and thus the solution it ran, exited unsuccessfully.
+    CargoFailed { command: String, status: ExitStatus },
+    /// Communication with adventofcode.com failed.
+    AocClient {
+        context: String,
+        source: AocClientError,
+    },
+    /// The timings file of a year could not be read, parsed or written.
+    Timings { path: String, reason: String },
+    /// The markers of a table in the README could not be located.
+    ReadmeMarkers(String),
+    /// Solutions returned answers that differ from their known answers.
+    WrongAnswers(usize),
+    /// Solutions timed out or crashed.
+    SolutionsFailed(usize),
+    /// A git command failed, e.g. because a revision does not exist.
+    Git { command: String, reason: String },
+    /// Any other IO failure.
+    Io { context: String, source: io::Error },
+}
+
+impl Error {
+    /// Create an IO error that describes what was attempted, e.g. `could not write "README.md"`.
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Error::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Create an error for a failed request to adventofcode.com, e.g. `failed to download puzzle`.
+    pub fn aoc_client(context: impl Into<String>, source: AocClientError) -> Self {
+        Error::AocClient {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// The exit code of the main binary when a subcommand fails with this error.
+    /// Codes start at `2`, so that they can't be confused with the generic failure code `1`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::MissingInput { .. } => 3,
+            Error::CargoSpawn { .. } => 4,
+            Error::CargoFailed { .. } => 5,
+            Error::AocClient { .. } => 6,
+            Error::Timings { .. } => 7,
+            Error::ReadmeMarkers(_) => 8,
+            Error::WrongAnswers(_) => 9,
+            Error::SolutionsFailed(_) => 10,
+            Error::Git { .. } => 11,
+            Error::Io { .. } => 12,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{message}"),
+            Error::MissingInput { path, source } => {
+                write!(f, "could not open input file \"{path}\": {source}")
+            }
+            Error::CargoSpawn { command, source } => {
+                write!(f, "could not run `{command}`: {source}")
+            }
+            Error::CargoFailed { command, status } => write!(f, "`{command}` failed: {status}"),
+            Error::AocClient { context, source } => write!(f, "{context}: {source}"),
+            Error::Timings { path, reason } => write!(f, "timings file \"{path}\": {reason}"),
+            Error::ReadmeMarkers(message) => write!(f, "README.md: {message}"),
+            Error::WrongAnswers(count) => write!(f, "{count} known answer(s) changed."),
+            Error::SolutionsFailed(count) => write!(f, "{count} part(s) timed out or crashed."),
+            Error::Git { command, reason } => write!(f, "`{command}` failed: {reason}"),
+            Error::Io { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::MissingInput { source, .. }
+            | Error::CargoSpawn { source, .. }
+            | Error::Io { source, .. } => Some(source),
+            Error::AocClient { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Error;
+    use std::{collections::HashSet, io, process::ExitStatus};
+
+    #[test]
+    fn reports_context() {
+        let error = Error::MissingInput {
+            path: "data/2024/inputs/01.txt".into(),
+            source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        };
+        assert_eq!(
+            error.to_string(),
+            "could not open input file \"data/2024/inputs/01.txt\": not found"
+        );
+        assert_eq!(error.exit_code(), 3);
+    }
+
+    #[test]
+    fn has_distinct_exit_codes() {
+        let errors = [
+            Error::io("could not write", io::Error::other("e")),
+            Error::Usage(String::new()),
+            Error::MissingInput {
+                path: String::new(),
+                source: io::Error::other("e"),
+            },
+            Error::CargoSpawn {
+                command: String::new(),
+                source: io::Error::other("e"),
+            },
+            Error::CargoFailed {
+                command: String::new(),
+                status: ExitStatus::default(),
+            },
+            Error::aoc_client(
+                "failed",
+                crate::template::aoc_client::AocClientError::MissingSession,
+            ),
+            Error::Timings {
+                path: String::new(),
+                reason: String::new(),
+            },
+            Error::ReadmeMarkers(String::new()),
+            Error::WrongAnswers(1),
+            Error::SolutionsFailed(1),
+            Error::Git {
+                command: String::new(),
+                reason: String::new(),
+            },
+        ];
+
+        let codes: HashSet<i32> = errors.iter().map(Error::exit_code).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&1));
+    }
+}
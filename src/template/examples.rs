@@ -0,0 +1,292 @@
+/// Extracts example inputs and their expected answers from the puzzle markdown in `data/<year>/puzzles`.
+///
+/// The example of a part is the first code block that follows a paragraph mentioning an "example".
+/// Its expected answer is the last emphasized code (``*`answer`*``) in the description of the part.
+use std::mem;
+
+/// The example of a single part.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    /// The example input, `None` if the part reuses the example of part one.
+    pub input: Option<String>,
+    /// The expected answer for the example input.
+    pub answer: Option<String>,
+}
+
+enum Block {
+    Paragraph(String),
+    Code(String),
+}
+
+/// Extract the examples of all parts present in a puzzle description.
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = split_parts(markdown)
+        .iter()
+        .map(|blocks| Example {
+            input: find_input(blocks),
+            answer: find_answer(blocks),
+        })
+        .collect();
+
+    // part two usually repeats the example of part one, only keep it when it differs.
+    if let [first, second, ..] = examples.as_mut_slice() {
+        if second.input.is_none() || second.input == first.input {
+            second.input = None;
+        }
+    }
+
+    examples
+}
+
+/// Split a puzzle description into the blocks of each part.
+fn split_parts(markdown: &str) -> Vec<Vec<Block>> {
+    let mut parts: Vec<Vec<Block>> = vec![];
+    let mut code: Option<Vec<&str>> = None;
+    let mut paragraph: Vec<&str> = vec![];
+
+    for line in markdown.lines() {
+        if let Some(lines) = &mut code {
+            if line == "```" {
+                push_block(&mut parts, Block::Code(lines.join("\n")));
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        let ends_paragraph =
+            line.starts_with("```") || line.trim().is_empty() || line.starts_with("## ");
+        if ends_paragraph && !paragraph.is_empty() {
+            push_block(
+                &mut parts,
+                Block::Paragraph(mem::take(&mut paragraph).join(" ")),
+            );
+        }
+
+        if line.starts_with("```") {
+            code = Some(vec![]);
+        } else if line.starts_with("## ") {
+            parts.push(vec![]);
+        } else if !line.trim().is_empty() {
+            paragraph.push(line);
+        }
+    }
+
+    if !paragraph.is_empty() {
+        push_block(&mut parts, Block::Paragraph(paragraph.join(" ")));
+    }
+
+    parts
+}
+
+fn push_block(parts: &mut Vec<Vec<Block>>, block: Block) {
+    match parts.last_mut() {
+        Some(blocks) => blocks.push(block),
+        None => parts.push(vec![block]),
+    }
+}
+
+fn find_input(blocks: &[Block]) -> Option<String> {
+    blocks.windows(2).find_map(|pair| match pair {
+        [Block::Paragraph(text), Block::Code(code)]
+            if text.to_lowercase().contains("example") && !code.trim().is_empty() =>
+        {
+            Some(format!("{code}\n"))
+        }
+        _ => None,
+    })
+}
+
+fn find_answer(blocks: &[Block]) -> Option<String> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Paragraph(text) if !text.starts_with("Your puzzle answer was") => Some(text),
+            _ => None,
+        })
+        .flat_map(|text| emphasized_code(text))
+        .last()
+}
+
+fn emphasized_code(text: &str) -> Vec<String> {
+    let mut found = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find("*`") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("`*") else {
+            break;
+        };
+        found.push(rest[..end].to_string());
+        rest = &rest[end + 2..];
+    }
+
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Fill the expected answers of the examples into the tests of a module created from a template in `src/templates`.
+/// Only assertions that still expect `None` are replaced, so edited tests are left alone.
+/// Answers are written as literals of `answer_type`, the `%ANSWER_TYPE%` the module was scaffolded with.
+pub fn fill_tests(module: &str, examples: &[Example], answer_type: &str) -> String {
+    let mut module = module.to_string();
+
+    for (example, test_name) in examples.iter().zip(["test_part_one", "test_part_two"]) {
+        let Some(start) = module.find(&format!("fn {test_name}()")) else {
+            continue;
+        };
+        let end = module[start..]
+            .find("#[test]")
+            .map_or(module.len(), |end| start + end);
+
+        let mut test = module[start..end].to_string();
+
+        if let Some(literal) = example
+            .answer
+            .as_deref()
+            .and_then(|answer| answer_literal(answer, answer_type))
+        {
+            test = test.replacen(
+                "assert_eq!(result, None);",
+                &format!("assert_eq!(result, {literal});"),
+                1,
+            );
+        }
+
+        if test_name == "test_part_two" && example.input.is_some() {
+            test = test.replacen(
+                "read_file(\"examples\", PUZZLE)",
+                "read_file_part(\"examples\", PUZZLE, 2)",
+                1,
+            );
+        }
+
+        module.replace_range(start..end, &test);
+    }
+
+    module
+}
+
+/// The expected value of a part returning `Option<answer_type>`, or `None` if the answer is no valid literal of that type.
+fn answer_literal(answer: &str, answer_type: &str) -> Option<String> {
+    let is_integer = answer.parse::<i128>().is_ok();
+    let is_unsigned = answer_type.starts_with('u');
+
+    match answer_type {
+        "String" => Some(format!("Some(String::from({answer:?}))")),
+        _ if is_integer && !(is_unsigned && answer.starts_with('-')) => {
+            Some(format!("Some({answer})"))
+        }
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::fs;
+
+    use super::{extract, fill_tests, Example};
+    use crate::template::commands::scaffold::{DEFAULT_TEMPLATE, TEMPLATES_DIR};
+
+    const PUZZLE_MARKDOWN: &str = "## --- Day 3: Mull It Over ---
+
+For example, consider the following section of corrupted memory:
+
+```
+xmul(2,4)%&mul[3,7]!
+
+do_not_mul(5,5)
+```
+
+Only the four highlighted sections are real `mul` instructions. Adding up the result of each instruction produces *`161`* (`2*4 + 5*5`).
+
+Your puzzle answer was `173517243`.
+
+## --- Part Two ---
+
+For example:
+
+```
+xmul(2,4)&mul[3,7]!^don't()
+```
+
+This time, the sum of the results is *`48`* (`2*4 + 8*5`).
+";
+
+    /// A module of day 3 scaffolded from the default template.
+    fn scaffolded_module(answer_type: &str) -> String {
+        fs::read_to_string(format!("{TEMPLATES_DIR}/{DEFAULT_TEMPLATE}.txt"))
+            .unwrap()
+            .replace("%TITLE%", "Day 3: Mull It Over")
+            .replace("%YEAR%", "2024")
+            .replace("%DAY_NUMBER%", "3")
+            .replace("%ANSWER_TYPE%", answer_type)
+            .replace("%EXAMPLE_1%", "read_file(\"examples\", PUZZLE)")
+            .replace("%EXAMPLE_2%", "read_file(\"examples\", PUZZLE)")
+    }
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(
+            extract(PUZZLE_MARKDOWN),
+            vec![
+                Example {
+                    input: Some("xmul(2,4)%&mul[3,7]!\n\ndo_not_mul(5,5)\n".into()),
+                    answer: Some("161".into()),
+                },
+                Example {
+                    input: Some("xmul(2,4)&mul[3,7]!^don't()\n".into()),
+                    answer: Some("48".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_repeated_examples() {
+        let markdown = "## --- Day 1 ---\n\nFor example:\n\n```\n3   4\n```\n\nA total of *`11`*.\n\n## --- Part Two ---\n\nHere is the same example again:\n\n```\n3   4\n```\n\nA score of *`31`*.\n";
+        let examples = extract(markdown);
+        assert_eq!(examples[1].input, None);
+        assert_eq!(examples[1].answer, Some("31".into()));
+    }
+
+    #[test]
+    fn fills_tests() {
+        let module = fill_tests(&scaffolded_module("u32"), &extract(PUZZLE_MARKDOWN), "u32");
+        assert!(module.contains("assert_eq!(result, Some(161));"));
+        assert!(module.contains("assert_eq!(result, Some(48));"));
+        assert!(module.contains("read_file_part(\"examples\", PUZZLE, 2)"));
+        assert_eq!(
+            fill_tests(&module, &extract(PUZZLE_MARKDOWN), "u32"),
+            module
+        );
+    }
+
+    #[test]
+    fn fills_answers_of_the_answer_type() {
+        let examples = [
+            Example {
+                input: None,
+                answer: Some("abc".into()),
+            },
+            Example {
+                input: None,
+                answer: Some("-5".into()),
+            },
+        ];
+
+        let module = fill_tests(&scaffolded_module("u32"), &examples, "u32");
+        assert_eq!(module, scaffolded_module("u32"));
+
+        let module = fill_tests(&scaffolded_module("String"), &examples, "String");
+        assert!(module.contains("assert_eq!(result, Some(String::from(\"abc\")));"));
+        assert!(module.contains("assert_eq!(result, Some(String::from(\"-5\")));"));
+
+        let module = fill_tests(&scaffolded_module("i64"), &examples, "i64");
+        assert!(module.contains("assert_eq!(result, Some(-5));"));
+    }
+}
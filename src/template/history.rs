@@ -0,0 +1,522 @@
+/// Append-only history of benchmark runs, used to detect performance regressions.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{Error, ErrorKind, Write},
+    process::Command,
+    str::FromStr,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::output::PARSE_PART;
+use crate::template::timings::{schema_version, Timing, Timings, SCHEMA_VERSION};
+use crate::template::{Day, Year};
+
+#[must_use]
+pub fn get_history_path(year: Year) -> String {
+    format!("./data/{year}/timings_history.jsonl")
+}
+
+/// Describes the machine a benchmark run was executed on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Machine {
+    pub hostname: Option<String>,
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+}
+
+impl Machine {
+    /// Detect the machine the current process runs on.
+    pub fn current() -> Self {
+        Machine {
+            hostname: fs::read_to_string("/etc/hostname")
+                .ok()
+                .or_else(|| std::env::var("COMPUTERNAME").ok())
+                .or_else(|| std::env::var("HOSTNAME").ok())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            os: std::env::consts::OS.into(),
+            arch: std::env::consts::ARCH.into(),
+            cpus: thread::available_parallelism().map_or(1, std::num::NonZero::get),
+        }
+    }
+}
+
+/// A single benchmark run, tagged with the commit and machine it was executed on.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub commit: Option<String>,
+    /// UTC date and time of the run in ISO 8601 format.
+    pub date: String,
+    pub machine: Machine,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Tag timings with the current commit, date and machine.
+    pub fn new(timings: Timings) -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        HistoryEntry {
+            commit: current_commit(),
+            date: format_utc_date(secs),
+            machine: Machine::current(),
+            timings,
+        }
+    }
+}
+
+/// Which previous run the current run is compared against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Baseline {
+    /// The most recent run of a part.
+    #[default]
+    Previous,
+    /// The fastest run of a part.
+    Best,
+}
+
+impl FromStr for Baseline {
+    type Err = BaselineFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "previous" => Ok(Baseline::Previous),
+            "best" => Ok(Baseline::Best),
+            _ => Err(BaselineFromStrError),
+        }
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Baseline::Previous => f.write_str("previous"),
+            Baseline::Best => f.write_str("best"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Baseline`].
+#[derive(Debug)]
+pub struct BaselineFromStrError;
+
+impl std::error::Error for BaselineFromStrError {}
+
+impl Display for BaselineFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a baseline of either `previous` or `best`")
+    }
+}
+
+/// A part that got slower than its baseline by more than the threshold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+}
+
+impl Regression {
+    /// Relative slowdown in percent.
+    pub fn change_pct(&self) -> f64 {
+        (self.current_nanos / self.baseline_nanos - 1.0) * 100.0
+    }
+}
+
+/// All benchmark runs stored so far, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Rehydrate the history of a year from its JSON lines file. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(get_history_path(year)) {
+            Ok(s) => History::from_str(&s),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Append a single run to the history file of a year without touching previous runs.
+    pub fn append_file(year: Year, entry: &HistoryEntry) -> Result<(), Error> {
+        let line = JsonValue::from(entry)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(get_history_path(year))?;
+
+        writeln!(file, "{line}")
+    }
+
+    /// Compare `current` against the baseline of each part, only considering runs from the same machine.
+    /// Returns the parts that got slower by more than `threshold_pct` percent.
+    pub fn find_regressions(
+        &self,
+        current: &Timings,
+        machine: &Machine,
+        baseline: Baseline,
+        threshold_pct: f64,
+    ) -> Vec<Regression> {
+        let mut regressions = vec![];
+
+        for timing in &current.data {
+            for part in [PARSE_PART, 1, 2] {
+                let Some(current_nanos) = timing.part_nanos(part) else {
+                    continue;
+                };
+
+                let Some(baseline_nanos) = self.baseline_nanos(timing.day, part, machine, baseline)
+                else {
+                    continue;
+                };
+
+                let regression = Regression {
+                    day: timing.day,
+                    part,
+                    baseline_nanos,
+                    current_nanos,
+                };
+
+                if regression.change_pct() > threshold_pct {
+                    regressions.push(regression);
+                }
+            }
+        }
+
+        regressions
+    }
+
+    fn baseline_nanos(
+        &self,
+        day: Day,
+        part: u8,
+        machine: &Machine,
+        baseline: Baseline,
+    ) -> Option<f64> {
+        let mut runs = self
+            .entries
+            .iter()
+            .filter(|entry| &entry.machine == machine)
+            .filter_map(|entry| {
+                entry
+                    .timings
+                    .data
+                    .iter()
+                    .find(|t| t.day == day)
+                    .and_then(|t| t.part_nanos(part))
+            });
+
+        match baseline {
+            Baseline::Previous => runs.next_back(),
+            Baseline::Best => runs.min_by(f64::total_cmp),
+        }
+    }
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/// Format seconds since the unix epoch as an ISO 8601 UTC date, e.g. `2024-12-01T05:00:00Z`.
+fn format_utc_date(secs: u64) -> String {
+    // see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = secs / 86400;
+    let secs_of_day = secs % 86400;
+
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for History {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(ix, line)| {
+                let json = JsonValue::from_str(line)
+                    .map_err(|_| format!("line {} is not valid JSON.", ix + 1))?;
+                HistoryEntry::try_from(&json).map_err(|e| format!("line {}: {e}", ix + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { entries })
+    }
+}
+
+impl From<&Machine> for JsonValue {
+    fn from(value: &Machine) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "hostname".into(),
+            value
+                .hostname
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("arch".into(), JsonValue::String(value.arch.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cpus".into(), JsonValue::Number(value.cpus as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Machine {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected machine.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cpus = json
+            .get("cpus")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected machine.cpus to be a number.")? as usize;
+
+        Ok(Machine {
+            hostname: json
+                .get("hostname")
+                .and_then(|v| v.get::<String>())
+                .cloned(),
+            os: string("os")?,
+            arch: string("arch")?,
+            cpus,
+        })
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
+        map.insert("date".into(), JsonValue::String(value.date.clone()));
+        map.insert("machine".into(), JsonValue::from(&value.machine));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let version = schema_version(json)?;
+
+        let date = json
+            .get("date")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected entry.date to be a string.")?;
+
+        let machine = json
+            .get("machine")
+            .ok_or("Expected entry.machine to be present.")
+            .map(Machine::try_from)??;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?
+            .iter()
+            .map(|t| Timing::from_json(t, version))
+            .collect::<Result<_, _>>()?;
+
+        Ok(HistoryEntry {
+            commit: json.get("commit").and_then(|v| v.get::<String>()).cloned(),
+            date,
+            machine,
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{format_utc_date, Baseline, History, HistoryEntry, Machine};
+    use crate::{
+        day,
+        template::timings::{tests::part, Timing, Timings},
+    };
+
+    fn machine(hostname: &str) -> Machine {
+        Machine {
+            hostname: Some(hostname.into()),
+            os: "linux".into(),
+            arch: "x86_64".into(),
+            cpus: 8,
+        }
+    }
+
+    fn timings(part_1_nanos: f64, part_2_nanos: f64) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(9),
+                parse: None,
+                part_1: part(part_1_nanos),
+                part_2: part(part_2_nanos),
+                total_nanos: part_1_nanos + part_2_nanos,
+            }],
+        }
+    }
+
+    fn entry(hostname: &str, timings: Timings) -> HistoryEntry {
+        HistoryEntry {
+            commit: Some("abc1234".into()),
+            date: "2024-12-09T05:00:00Z".into(),
+            machine: machine(hostname),
+            timings,
+        }
+    }
+
+    fn get_mock_history() -> History {
+        History {
+            entries: vec![
+                entry("a", timings(100.0, 1000.0)),
+                entry("a", timings(200.0, 2000.0)),
+                entry("b", timings(10.0, 10.0)),
+            ],
+        }
+    }
+
+    #[test]
+    fn formats_utc_dates() {
+        assert_eq!(format_utc_date(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc_date(1_733_029_200), "2024-12-01T05:00:00Z");
+        assert_eq!(format_utc_date(951_782_400), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let history = get_mock_history();
+        let lines: Vec<String> = history
+            .entries
+            .iter()
+            .map(|e| JsonValue::from(e).stringify().unwrap())
+            .collect();
+        let parsed = History::from_str(&lines.join("\n")).unwrap();
+        assert_eq!(parsed.entries.len(), 3);
+        assert_eq!(parsed.entries[0].commit, Some("abc1234".into()));
+        assert_eq!(parsed.entries[2].machine, machine("b"));
+        assert_eq!(
+            parsed.entries[1].timings.data[0].part_nanos(2),
+            Some(2000.0)
+        );
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let err = History::from_str("\n{}\n").unwrap_err();
+        assert!(err.starts_with("line 1"));
+    }
+
+    #[test]
+    fn detects_regressions_against_previous() {
+        let history = get_mock_history();
+        let regressions = history.find_regressions(
+            &timings(150.0, 2500.0),
+            &machine("a"),
+            Baseline::Previous,
+            10.0,
+        );
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].part, 2);
+        assert_eq!(regressions[0].baseline_nanos, 2000.0);
+        assert_eq!(regressions[0].change_pct(), 25.0);
+    }
+
+    #[test]
+    fn detects_regressions_against_best() {
+        let history = get_mock_history();
+        let regressions =
+            history.find_regressions(&timings(150.0, 1050.0), &machine("a"), Baseline::Best, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].part, 1);
+        assert_eq!(regressions[0].baseline_nanos, 100.0);
+    }
+
+    #[test]
+    fn ignores_other_machines() {
+        let history = get_mock_history();
+        let regressions =
+            history.find_regressions(&timings(1e9, 1e9), &machine("c"), Baseline::Best, 10.0);
+        assert_eq!(regressions.is_empty(), true);
+    }
+}
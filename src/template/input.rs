@@ -0,0 +1,129 @@
+/// Selects the input of a solution binary from its command-line arguments.
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read},
+};
+
+use crate::template::{Error, PuzzleId};
+
+/// Where a solution binary reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/<year>/inputs`.
+    #[default]
+    Puzzle,
+    /// The example in `data/<year>/examples`, or the example of a part with `--example <part>`.
+    Example(Option<u8>),
+    /// Any file passed with `--input <path>`.
+    File(String),
+    /// Standard input, selected with `-`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parse the input source from the arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let position = |name: &str| args.iter().position(|arg| arg == name);
+
+        let mut sources = vec![];
+
+        if let Some(ix) = position("--input") {
+            match args.get(ix + 1) {
+                Some(path) => sources.push(InputSource::File(path.clone())),
+                None => return Err("`--input` expects a path.".into()),
+            }
+        }
+
+        if let Some(ix) = position("--example") {
+            let part = args.get(ix + 1).and_then(|arg| arg.parse().ok());
+            sources.push(InputSource::Example(part));
+        }
+
+        if position("-").is_some() {
+            sources.push(InputSource::Stdin);
+        }
+
+        match sources.len() {
+            0 => Ok(InputSource::Puzzle),
+            1 => Ok(sources.remove(0)),
+            _ => Err("only one of `--input <path>`, `--example [N]` and `-` can be used.".into()),
+        }
+    }
+
+    /// Whether this is the input that answers are submitted and checked for.
+    pub fn is_puzzle_input(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// Read the input of a puzzle from this source.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        let path = match self {
+            InputSource::Puzzle => puzzle.input_path(),
+            InputSource::Example(None) => puzzle.example_path(),
+            InputSource::Example(Some(part)) => puzzle.data_part_path("examples", *part),
+            InputSource::File(path) => path.clone(),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                return io::stdin()
+                    .read_to_string(&mut input)
+                    .map(|_| input)
+                    .map_err(|e| Error::io("could not read input from stdin", e));
+            }
+        };
+
+        fs::read_to_string(&path).map_err(|source| Error::MissingInput { path, source })
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "the puzzle input"),
+            InputSource::Example(None) => write!(f, "the example"),
+            InputSource::Example(Some(part)) => write!(f, "example {part}"),
+            InputSource::File(path) => write!(f, "\"{path}\""),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+
+    fn parse(args: &[&str]) -> Result<InputSource, String> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        InputSource::from_args(&args)
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse(&["2024_01"]), Ok(InputSource::Puzzle));
+        assert_eq!(
+            parse(&["2024_01", "--input", "stress.txt", "--time"]),
+            Ok(InputSource::File("stress.txt".into()))
+        );
+        assert_eq!(
+            parse(&["2024_01", "--example"]),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            parse(&["2024_01", "--example", "2", "--format", "json"]),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert_eq!(
+            parse(&["2024_01", "--example", "--time"]),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(parse(&["2024_01", "-"]), Ok(InputSource::Stdin));
+    }
+
+    #[test]
+    fn rejects_conflicting_sources() {
+        assert!(parse(&["2024_01", "--input"]).is_err());
+        assert!(parse(&["2024_01", "--example", "-"]).is_err());
+    }
+}
@@ -0,0 +1,545 @@
+/// Local record of submitted answers, used to avoid wasting submissions on answers that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{AnswerHint, SubmissionOutcome};
+use crate::template::{Day, PuzzleId, Year};
+
+static LEDGER_FILE_PATH: &str = "./data/submissions.json";
+
+/// The server's verdict on a submission, as stored in the ledger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint whether the answer is too high or too low.
+    Incorrect,
+    /// The answer was not checked because of the cooldown.
+    RateLimited,
+    /// The part was already solved or not unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            "wrong_level" => Ok(Verdict::WrongLevel),
+            v => Err(format!("unknown verdict `{v}`.")),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Cooldown imposed by the server after this submission.
+    pub wait_seconds: Option<u64>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    pub fn new(
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+        outcome: &SubmissionOutcome,
+        timestamp: u64,
+    ) -> Self {
+        let (verdict, wait_seconds) = match outcome {
+            SubmissionOutcome::Correct => (Verdict::Correct, None),
+            SubmissionOutcome::Incorrect { hint, wait_seconds } => (
+                match hint {
+                    Some(AnswerHint::TooHigh) => Verdict::TooHigh,
+                    Some(AnswerHint::TooLow) => Verdict::TooLow,
+                    None => Verdict::Incorrect,
+                },
+                *wait_seconds,
+            ),
+            SubmissionOutcome::RateLimited { wait_seconds } => {
+                (Verdict::RateLimited, Some(*wait_seconds))
+            }
+            SubmissionOutcome::WrongLevel => (Verdict::WrongLevel, None),
+        };
+
+        Submission {
+            puzzle,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            wait_seconds,
+            timestamp,
+        }
+    }
+}
+
+/// Reasons to not submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved with this or another answer.
+    AlreadySolved { answer: String },
+    /// The same answer was submitted before and was wrong.
+    KnownWrong { verdict: Verdict },
+    /// The server's cooldown after the last submission has not passed yet.
+    CoolingDown { seconds_left: u64 },
+    /// The ledger could not be read, so none of the other checks can be made.
+    Unreadable { reason: String },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with answer `{answer}`.")
+            }
+            Refusal::KnownWrong { verdict } => {
+                write!(f, "this answer was submitted before and was {verdict}.")
+            }
+            Refusal::CoolingDown { seconds_left } => write!(
+                f,
+                "the server asked to wait before submitting again, {seconds_left}s left."
+            ),
+            Refusal::Unreadable { reason } => write!(
+                f,
+                "the submission ledger can't be read: {reason} Pass `--ignore-ledger` to submit without its checks."
+            ),
+        }
+    }
+}
+
+/// Check an answer against the ledger returned by [`Ledger::read_from_file`].
+/// A ledger that can't be read refuses every answer, unless `ignore_unreadable` is set to submit without its checks.
+pub fn check_submission(
+    ledger: &Result<Ledger, String>,
+    ignore_unreadable: bool,
+    puzzle: PuzzleId,
+    part: u8,
+    answer: &str,
+    now: u64,
+) -> Result<Option<BoundsWarning>, Refusal> {
+    match ledger {
+        Ok(ledger) => ledger.check(puzzle, part, answer, now),
+        Err(_) if ignore_unreadable => Ok(None),
+        Err(reason) => Err(Refusal::Unreadable {
+            reason: reason.clone(),
+        }),
+    }
+}
+
+/// Warnings about an answer that is still submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoundsWarning {
+    /// The answer is at least as high as an answer that was too high.
+    AboveUpperBound { bound: i128 },
+    /// The answer is at most as low as an answer that was too low.
+    BelowLowerBound { bound: i128 },
+}
+
+impl Display for BoundsWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundsWarning::AboveUpperBound { bound } => {
+                write!(f, "answer is not below `{bound}`, which was too high.")
+            }
+            BoundsWarning::BelowLowerBound { bound } => {
+                write!(f, "answer is not above `{bound}`, which was too low.")
+            }
+        }
+    }
+}
+
+/// All submissions made so far, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Rehydrate the ledger from its JSON file. If not present, returns an empty ledger.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(LEDGER_FILE_PATH) {
+            Ok(s) => Ledger::try_from(s),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Dehydrate the ledger to its JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(LEDGER_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    /// Submissions for one part of a puzzle.
+    pub fn for_part(&self, puzzle: PuzzleId, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |s| s.puzzle == puzzle && s.part == part)
+    }
+
+    /// The accepted answer of a part, if any.
+    pub fn correct_answer(&self, puzzle: PuzzleId, part: u8) -> Option<&str> {
+        self.for_part(puzzle, part)
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Check whether an answer should be submitted at time `now` (seconds since the unix epoch).
+    /// Returns a warning if a numeric answer is outside of the bounds established by previous hints.
+    pub fn check(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+        now: u64,
+    ) -> Result<Option<BoundsWarning>, Refusal> {
+        if let Some(correct) = self.correct_answer(puzzle, part) {
+            return Err(Refusal::AlreadySolved {
+                answer: correct.to_string(),
+            });
+        }
+
+        if let Some(previous) = self
+            .for_part(puzzle, part)
+            .find(|s| s.answer == answer && s.verdict.is_wrong())
+        {
+            return Err(Refusal::KnownWrong {
+                verdict: previous.verdict,
+            });
+        }
+
+        // NOTE: the cooldown applies to all puzzles of an event.
+        let cooldown_end = self
+            .submissions
+            .iter()
+            .filter(|s| s.puzzle.year == puzzle.year)
+            .filter_map(|s| s.wait_seconds.map(|wait| s.timestamp + wait))
+            .max();
+
+        if let Some(end) = cooldown_end.filter(|end| *end > now) {
+            return Err(Refusal::CoolingDown {
+                seconds_left: end - now,
+            });
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(None);
+        };
+
+        let numeric = |verdict: Verdict| {
+            self.for_part(puzzle, part)
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.trim().parse::<i128>().ok())
+        };
+
+        if let Some(bound) = numeric(Verdict::TooHigh).min().filter(|b| value >= *b) {
+            return Ok(Some(BoundsWarning::AboveUpperBound { bound }));
+        }
+
+        if let Some(bound) = numeric(Verdict::TooLow).max().filter(|b| value <= *b) {
+            return Ok(Some(BoundsWarning::BelowLowerBound { bound }));
+        }
+
+        Ok(None)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Ledger> for JsonValue {
+    fn from(value: &Ledger) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.submissions.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Ledger {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let submissions = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("submissions")
+            .ok_or("expected JSON document to have key `submissions`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.submissions` to be an array.")?;
+
+        Ok(Ledger {
+            submissions: submissions
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.puzzle.year.into_inner())),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "wait_seconds".into(),
+            value
+                .wait_seconds
+                .map_or(JsonValue::Null, |w| JsonValue::Number(w as f64)),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("Expected submission.{key} to be a string."))
+        };
+
+        let day = Day::from_str(string("day")?)
+            .map_err(|_| "Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = Year::new(number("year")? as u16)
+            .ok_or("Expected submission.year to be a valid year.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Submission {
+            puzzle: PuzzleId::new(year, day),
+            part: number("part")? as u8,
+            answer: string("answer")?.clone(),
+            verdict: Verdict::from_str(string("verdict")?)?,
+            wait_seconds: json
+                .get("wait_seconds")
+                .and_then(|v| v.get::<f64>())
+                .map(|w| *w as u64),
+            timestamp: number("timestamp")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_submission, BoundsWarning, Ledger, Refusal, Submission, Verdict};
+    use crate::{
+        puzzle,
+        template::aoc_client::{AnswerHint, SubmissionOutcome},
+    };
+
+    fn wrong(answer: &str, hint: Option<AnswerHint>, timestamp: u64) -> Submission {
+        Submission::new(
+            puzzle!(2024, 5),
+            1,
+            answer,
+            &SubmissionOutcome::Incorrect {
+                hint,
+                wait_seconds: Some(60),
+            },
+            timestamp,
+        )
+    }
+
+    fn get_mock_ledger() -> Ledger {
+        Ledger {
+            submissions: vec![
+                wrong("100", Some(AnswerHint::TooHigh), 1000),
+                wrong("10", Some(AnswerHint::TooLow), 2000),
+                wrong("50", None, 3000),
+            ],
+        }
+    }
+
+    #[test]
+    fn maps_outcomes_to_verdicts() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.submissions[0].verdict, Verdict::TooHigh);
+        assert_eq!(ledger.submissions[1].verdict, Verdict::TooLow);
+        assert_eq!(ledger.submissions[2].verdict, Verdict::Incorrect);
+        assert_eq!(ledger.submissions[2].wait_seconds, Some(60));
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(puzzle!(2024, 5), 1, "50", 10_000),
+            Err(Refusal::KnownWrong {
+                verdict: Verdict::Incorrect
+            })
+        );
+        assert_eq!(ledger.check(puzzle!(2024, 5), 2, "50", 10_000), Ok(None));
+        assert_eq!(ledger.check(puzzle!(2023, 5), 1, "50", 10_000), Ok(None));
+    }
+
+    #[test]
+    fn refuses_during_cooldown() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(puzzle!(2024, 6), 1, "1", 3010),
+            Err(Refusal::CoolingDown { seconds_left: 50 })
+        );
+        assert_eq!(ledger.check(puzzle!(2024, 6), 1, "1", 3060), Ok(None));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut ledger = get_mock_ledger();
+        ledger.record(Submission::new(
+            puzzle!(2024, 5),
+            1,
+            "42",
+            &SubmissionOutcome::Correct,
+            4000,
+        ));
+        assert_eq!(ledger.correct_answer(puzzle!(2024, 5), 1), Some("42"));
+        assert_eq!(
+            ledger.check(puzzle!(2024, 5), 1, "43", 10_000),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+
+    #[test]
+    fn warns_outside_of_bounds() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(puzzle!(2024, 5), 1, "150", 10_000),
+            Ok(Some(BoundsWarning::AboveUpperBound { bound: 100 }))
+        );
+        assert_eq!(
+            ledger.check(puzzle!(2024, 5), 1, "5", 10_000),
+            Ok(Some(BoundsWarning::BelowLowerBound { bound: 10 }))
+        );
+        assert_eq!(ledger.check(puzzle!(2024, 5), 1, "42", 10_000), Ok(None));
+        assert_eq!(ledger.check(puzzle!(2024, 5), 1, "abc", 10_000), Ok(None));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let ledger = get_mock_ledger();
+        let json = tinyjson::JsonValue::from(&ledger).stringify().unwrap();
+        let parsed = Ledger::try_from(json).unwrap();
+        assert_eq!(parsed.submissions, ledger.submissions);
+    }
+
+    #[test]
+    fn refuses_with_unreadable_ledger() {
+        let ledger = Ledger::try_from("{\"submissions\": [".to_string());
+        assert_eq!(
+            check_submission(&ledger, false, puzzle!(2024, 5), 1, "42", 10_000),
+            Err(Refusal::Unreadable {
+                reason: "not valid JSON file.".into()
+            })
+        );
+        assert_eq!(
+            check_submission(&ledger, true, puzzle!(2024, 5), 1, "42", 10_000),
+            Ok(None)
+        );
+        assert_eq!(
+            check_submission(
+                &Ok(get_mock_ledger()),
+                true,
+                puzzle!(2024, 5),
+                1,
+                "50",
+                10_000
+            ),
+            Err(Refusal::KnownWrong {
+                verdict: Verdict::Incorrect
+            })
+        );
+    }
+}
@@ -1,68 +1,151 @@
-use std::{env, fs};
+use std::fs;
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod heap;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use error::Error;
+pub use history::Baseline;
+pub use output::{OutputFormat, PARSE_PART};
+pub use puzzle_id::*;
+pub use readme_benchmarks::{TableOptions, TableOrder};
+pub use run_multi::{Execution, RunOptions, Timeouts};
+pub use year::*;
 
 mod day;
+mod error;
+mod examples;
+mod history;
+mod input;
+mod ledger;
+mod output;
+mod parallel;
+mod puzzle_id;
+mod readme;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod stats;
 mod timings;
+mod year;
+
+/// Heap profiling is enabled for every binary that links the template, see `cargo solve --dhat`.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+/// Allocation counting is enabled for every binary that links the template, see `cargo time --alloc`.
+#[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: heap::CountingAlloc = heap::CountingAlloc;
+
+#[cfg(all(feature = "alloc-stats", feature = "dhat-heap"))]
+compile_error!("the `alloc-stats` and `dhat-heap` features both install a global allocator and can't be combined.");
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_CLEAR_LINE: &str = "\r\x1b[2K";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+/// Helper function that reads a text file of a puzzle to a string. E.g. like `data/2024/inputs/01.txt`.
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> Result<String, Error> {
+    read_path(puzzle.data_path(folder))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> Result<String, Error> {
+    read_path(puzzle.data_part_path(folder, part))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+fn read_path(path: String) -> Result<String, Error> {
+    fs::read_to_string(&path).map_err(|source| Error::MissingInput { path, source })
+}
+
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// The parts are also registered as `SOLUTION`, which the runner binary uses to run all days in one process.
+///
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// With `parse` as third parameter, the input is passed through a function `parse(input: &str)` once,
+/// and a reference to its output is passed to both parts. Parsing is then timed separately from the parts.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [part_two, 2]);
+    };
+    ($year:expr, $day:expr, parse) => {
+        $crate::solution!(@parse $year, $day, [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, parse, 1) => {
+        $crate::solution!(@parse $year, $day, [part_one, 1]);
+    };
+    ($year:expr, $day:expr, parse, 2) => {
+        $crate::solution!(@parse $year, $day, [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl $year:expr, $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@constants $year, $day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The parts of this solution, see [`$crate::template::registry`].
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle: PUZZLE,
+            parts: &[$( ($part, |input: &str| $crate::template::runner::solve_part($func, input, PUZZLE, $part)), )*],
+        };
 
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = read_input(PUZZLE);
+            print_begin_event(&SOLUTION);
+            $( run_part($func, &input, PUZZLE, $part); )*
         }
     };
+
+    (@parse $year:expr, $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@constants $year, $day);
+
+        /// The parse step and parts of this solution, see [`$crate::template::registry`].
+        /// Each part parses the input again, outside of its timing.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle: PUZZLE,
+            parts: &[
+                ($crate::template::PARSE_PART, |input: &str| $crate::template::runner::solve_parse(parse, input, PUZZLE)),
+                $( ($part, |input: &str| $crate::template::runner::solve_part($func, &parse(input), PUZZLE, $part)), )*
+            ],
+        };
+
+        #[allow(dead_code)]
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(PUZZLE);
+            print_begin_event(&SOLUTION);
+            let parsed = run_parse(parse, input.as_str(), PUZZLE);
+            $( run_part($func, &parsed, PUZZLE, $part); )*
+        }
+    };
+
+    (@constants $year:expr, $day:expr) => {
+        /// The year of the event.
+        #[allow(dead_code)]
+        const YEAR: $crate::template::Year = $crate::year!($year);
+        /// The current day.
+        #[allow(dead_code)]
+        const DAY: $crate::template::Day = $crate::day!($day);
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
+    };
 }
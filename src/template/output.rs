@@ -0,0 +1,474 @@
+/// Output formats of the solution runner and the structured record emitted per solution part.
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::answers::AnswerCheck;
+use crate::template::heap::AllocStats;
+use crate::template::stats::BenchStats;
+use crate::template::Day;
+
+/// Controls how solution results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output with ANSI formatting.
+    #[default]
+    Text,
+    /// One JSON record per solution part and line.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => f.write_str("text"),
+            OutputFormat::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl std::error::Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of either `text` or `json`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The part number of the optional parse step of a solution, see `solution!(year, day, parse)`.
+pub const PARSE_PART: u8 = 0;
+
+/// The human-readable name of a part, e.g. `Part 1` or `Parse`.
+pub fn part_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// How a solution part ended.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned, with or without an answer.
+    #[default]
+    Finished,
+    /// The part exceeded its timeout and its process was killed.
+    TimedOut,
+    /// The part panicked or its process exited unsuccessfully.
+    Crashed,
+}
+
+impl PartStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PartStatus::Finished => "finished",
+            PartStatus::TimedOut => "timed_out",
+            PartStatus::Crashed => "crashed",
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "finished" => Ok(PartStatus::Finished),
+            "timed_out" => Ok(PartStatus::TimedOut),
+            "crashed" => Ok(PartStatus::Crashed),
+            s => Err(format!("Unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// The outcome of running a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration_nanos: f64,
+    pub samples: u128,
+    /// Sample distribution, only present if the part was benched.
+    pub stats: Option<BenchStats>,
+    /// Heap usage of the first execution, only present with the `alloc-stats` feature.
+    pub alloc: Option<AllocStats>,
+    /// Comparison of the answer against the known answer of the part.
+    pub check: AnswerCheck,
+    pub status: PartStatus,
+}
+
+impl PartResult {
+    /// A part that timed out or crashed after running for `duration_nanos`.
+    pub fn failed(day: Day, part: u8, status: PartStatus, duration_nanos: f64) -> Self {
+        PartResult {
+            day,
+            part,
+            answer: None,
+            duration_nanos,
+            samples: 0,
+            stats: None,
+            alloc: None,
+            check: AnswerCheck::Unknown,
+            status,
+        }
+    }
+
+    /// Whether the part timed out or crashed.
+    pub fn is_failure(&self) -> bool {
+        self.status != PartStatus::Finished
+    }
+
+    /// Whether this is the parse step of a solution rather than one of its parts.
+    pub fn is_parse(&self) -> bool {
+        self.part == PARSE_PART
+    }
+
+    /// A part is successful if the solution returned an answer. The parse step has no answer and is successful if it finished.
+    pub fn is_success(&self) -> bool {
+        self.answer.is_some() || (self.is_parse() && !self.is_failure())
+    }
+
+    /// Serialize the result to a single-line JSON record.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part result should be serializable")
+    }
+}
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "day".into(),
+            JsonValue::Number(f64::from(value.day.into_inner())),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("success".into(), JsonValue::Boolean(value.is_success()));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "alloc".into(),
+            value
+                .alloc
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "check".into(),
+            JsonValue::String(value.check.as_str().into()),
+        );
+        map.insert(
+            "expected".into(),
+            match &value.check {
+                AnswerCheck::Wrong { expected } => JsonValue::String(expected.clone()),
+                _ => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part result to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected result.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let day = Day::new(number("day")? as u8).ok_or("Expected result.day to be a Day.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = number("part")? as u8;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?
+            .cloned();
+
+        let stats = json
+            .get("stats")
+            .filter(|v| !v.is_null())
+            .map(BenchStats::try_from)
+            .transpose()?;
+
+        let alloc = json
+            .get("alloc")
+            .filter(|v| !v.is_null())
+            .map(AllocStats::try_from)
+            .transpose()?;
+
+        // records of older runners don't contain a check.
+        let check = match json.get("check").and_then(|v| v.get::<String>()) {
+            None => AnswerCheck::Unknown,
+            Some(check) => match check.as_str() {
+                "correct" => AnswerCheck::Correct,
+                "wrong" => AnswerCheck::Wrong {
+                    expected: json
+                        .get("expected")
+                        .and_then(|v| v.get::<String>())
+                        .cloned()
+                        .ok_or("Expected result.expected to be a string.")?,
+                },
+                "unknown" => AnswerCheck::Unknown,
+                c => return Err(format!("Unknown answer check `{c}`.")),
+            },
+        };
+
+        // records of older runners don't contain a status.
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .map_or(Ok(PartStatus::Finished), |status| status.parse())?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartResult {
+            day,
+            part,
+            answer,
+            duration_nanos: number("duration_nanos")?,
+            samples: number("samples")? as u128,
+            stats,
+            alloc,
+            check,
+            status,
+        })
+    }
+}
+
+impl FromStr for PartResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).map_err(|e| e.to_string())?;
+        PartResult::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Progress records that solution binaries print with `--events`, so that `run_multi` can enforce timeouts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunnerEvent {
+    /// A day started, it runs the given parts in this order.
+    Begin { day: Day, parts: Vec<u8> },
+    /// A part started running.
+    Start { day: Day, part: u8 },
+    /// All results of a day were printed.
+    Done { day: Day },
+}
+
+impl RunnerEvent {
+    /// Serialize the event to a single-line JSON record.
+    pub fn to_json_line(&self) -> String {
+        match self {
+            RunnerEvent::Begin { day, parts } => {
+                let parts: Vec<String> = parts.iter().map(u8::to_string).collect();
+                format!(
+                    r#"{{"event":"begin","day":{},"parts":[{}]}}"#,
+                    day.into_inner(),
+                    parts.join(",")
+                )
+            }
+            RunnerEvent::Start { day, part } => {
+                format!(
+                    r#"{{"event":"start","day":{},"part":{part}}}"#,
+                    day.into_inner()
+                )
+            }
+            RunnerEvent::Done { day } => {
+                format!(r#"{{"event":"done","day":{}}}"#, day.into_inner())
+            }
+        }
+    }
+}
+
+impl FromStr for RunnerEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).map_err(|e| e.to_string())?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected event to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|n| n as u8)
+                .ok_or(format!("Expected event.{key} to be a number."))
+        };
+
+        let day = Day::new(number("day")?).ok_or("Expected event.day to be a Day.")?;
+
+        match json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            Some("begin") => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let parts = json
+                    .get("parts")
+                    .and_then(|v| v.get::<Vec<JsonValue>>())
+                    .and_then(|parts| {
+                        parts
+                            .iter()
+                            .map(|part| part.get::<f64>().map(|n| *n as u8))
+                            .collect::<Option<Vec<u8>>>()
+                    })
+                    .ok_or("Expected event.parts to be an array of numbers.")?;
+                Ok(RunnerEvent::Begin { day, parts })
+            }
+            Some("start") => Ok(RunnerEvent::Start {
+                day,
+                part: number("part")?,
+            }),
+            Some("done") => Ok(RunnerEvent::Done { day }),
+            _ => Err("Expected event.event to be `begin`, `start` or `done`.".into()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{OutputFormat, PartResult, PartStatus, RunnerEvent};
+    use crate::{
+        day,
+        template::{answers::AnswerCheck, heap::AllocStats},
+    };
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!(OutputFormat::from_str("text").unwrap(), OutputFormat::Text);
+        assert_eq!(OutputFormat::from_str("json").unwrap(), OutputFormat::Json);
+        assert!(OutputFormat::from_str("yaml").is_err());
+    }
+
+    #[test]
+    fn roundtrips_part_results() {
+        let result = PartResult {
+            day: day!(9),
+            part: 2,
+            answer: Some("6398096697992".into()),
+            duration_nanos: 73_700_000.0,
+            samples: 14,
+            stats: None,
+            alloc: Some(AllocStats {
+                allocations: 1001,
+                bytes: 96_000,
+                peak_bytes: 24_000,
+            }),
+            check: AnswerCheck::Wrong {
+                expected: "6398096697991".into(),
+            },
+            status: PartStatus::Finished,
+        };
+        let line = result.to_json_line();
+        assert!(!line.contains('\n'));
+        assert!(line.contains(r#""success":true"#));
+        assert_eq!(PartResult::from_str(&line).unwrap(), result);
+    }
+
+    #[test]
+    fn parses_failed_parts() {
+        let line = r#"{"day":1,"part":1,"answer":null,"duration_nanos":100,"samples":1,"success":false,"stats":null}"#;
+        let result = PartResult::from_str(line).unwrap();
+        assert_eq!(result.day, day!(1));
+        assert_eq!(result.answer, None);
+        assert_eq!(result.is_success(), false);
+        assert_eq!(result.check, AnswerCheck::Unknown);
+        assert_eq!(result.status, PartStatus::Finished);
+    }
+
+    #[test]
+    fn roundtrips_timed_out_parts() {
+        let result = PartResult::failed(day!(14), 2, PartStatus::TimedOut, 1e10);
+        assert!(result.is_failure());
+        assert_eq!(
+            PartResult::from_str(&result.to_json_line()).unwrap(),
+            result
+        );
+    }
+
+    #[test]
+    fn roundtrips_runner_events() {
+        for event in [
+            RunnerEvent::Begin {
+                day: day!(3),
+                parts: vec![0, 2],
+            },
+            RunnerEvent::Start {
+                day: day!(6),
+                part: 2,
+            },
+            RunnerEvent::Done { day: day!(25) },
+        ] {
+            assert_eq!(RunnerEvent::from_str(&event.to_json_line()), Ok(event));
+        }
+        assert!(RunnerEvent::from_str(r#"{"day":1,"part":1,"answer":null}"#).is_err());
+    }
+
+    #[test]
+    fn rejects_screen_output() {
+        assert!(PartResult::from_str("Part 1: 0 (74.13ns @ 100000 samples)").is_err());
+        assert!(PartResult::from_str(r#"{ "day": 26, "part": 1 }"#).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+/// Runs work items on a fixed number of threads while emitting their results in input order.
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Run `work` for each item on up to `jobs` threads and pass the results to `emit` in the order of `items`.
+/// A result is emitted as soon as all results before it are emitted. With a single job, items run sequentially on the current thread.
+pub fn run_ordered<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(&T, R),
+) {
+    if jobs <= 1 || items.len() <= 1 {
+        items.iter().for_each(|item| emit(item, work(item)));
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel::<(usize, R)>();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let next = &next;
+            let work = &work;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if tx.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // NOTE: drop the original sender so that the receiver finishes once all workers are done.
+        drop(tx);
+
+        let mut buffered: HashMap<usize, R> = HashMap::new();
+        let mut next_to_emit = 0;
+
+        for (index, result) in rx {
+            buffered.insert(index, result);
+            while let Some(result) = buffered.remove(&next_to_emit) {
+                emit(&items[next_to_emit], result);
+                next_to_emit += 1;
+            }
+        }
+    });
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::run_ordered;
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn emits_in_order() {
+        let items: Vec<u64> = (0..8).collect();
+        let mut emitted = vec![];
+
+        run_ordered(
+            &items,
+            4,
+            |item| {
+                // later items finish first.
+                thread::sleep(Duration::from_millis(40 - item * 5));
+                item * 2
+            },
+            |item, result| emitted.push((*item, result)),
+        );
+
+        assert_eq!(
+            emitted,
+            items.iter().map(|i| (*i, i * 2)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn runs_sequentially_with_one_job() {
+        let items = vec![1, 2, 3];
+        let mut emitted = vec![];
+        run_ordered(&items, 1, |item| item + 1, |_, result| emitted.push(result));
+        assert_eq!(emitted, vec![2, 3, 4]);
+    }
+}
@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{Day, Year};
+
+/// Identifies a puzzle by the year of its event and its day.
+///
+/// # Display
+/// This value displays as `<year>/<day>`.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2024).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024/08");
+/// assert_eq!(puzzle.bin_name(), "2024_08");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Name of the binary target that contains the solution.
+    pub fn bin_name(&self) -> String {
+        format!("{}_{}", self.year, self.day)
+    }
+
+    /// Path of the solution's source file.
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// Directory that contains all data of the puzzle's year.
+    pub fn data_dir(&self) -> String {
+        format!("data/{}", self.year)
+    }
+
+    /// Path of a text file in a data folder of the puzzle's year, e.g. `data/2024/inputs/08.txt`.
+    pub fn data_path(&self, folder: &str) -> String {
+        format!("{}/{folder}/{}.txt", self.data_dir(), self.day)
+    }
+
+    /// Path of a text file for a single part in a data folder of the puzzle's year, e.g. `data/2024/examples/08-2.txt`.
+    pub fn data_part_path(&self, folder: &str, part: u8) -> String {
+        format!("{}/{folder}/{}-{part}.txt", self.data_dir(), self.day)
+    }
+
+    pub fn input_path(&self) -> String {
+        self.data_path("inputs")
+    }
+
+    pub fn example_path(&self) -> String {
+        self.data_path("examples")
+    }
+
+    pub fn puzzle_path(&self) -> String {
+        format!("{}/puzzles/{}.md", self.data_dir(), self.day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s.split_once('/').ok_or(PuzzleIdFromStrError)?;
+        Ok(Self {
+            year: year.parse().map_err(|_| PuzzleIdFromStrError)?,
+            day: day.parse().map_err(|_| PuzzleIdFromStrError)?,
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a puzzle in the format `<year>/<day>`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {
+        $crate::template::PuzzleId::new($crate::year!($year), $crate::day!($day))
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use std::str::FromStr;
+
+    #[test]
+    fn builds_paths() {
+        let puzzle = puzzle!(2024, 3);
+        assert_eq!(puzzle.bin_path(), "./src/bin/2024_03.rs");
+        assert_eq!(puzzle.input_path(), "data/2024/inputs/03.txt");
+        assert_eq!(
+            puzzle.data_part_path("examples", 2),
+            "data/2024/examples/03-2.txt"
+        );
+        assert_eq!(puzzle.puzzle_path(), "data/2024/puzzles/03.md");
+    }
+
+    #[test]
+    fn parses_puzzle_ids() {
+        assert_eq!(PuzzleId::from_str("2023/7").unwrap(), puzzle!(2023, 7));
+        assert!(PuzzleId::from_str("2023").is_err());
+        assert!(PuzzleId::from_str("2023/26").is_err());
+    }
+}
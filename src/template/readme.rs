@@ -0,0 +1,59 @@
+/// Rewrites the generated tables of the readme, see `cargo readme`.
+use std::fs;
+
+use crate::template::timings::Timings;
+use crate::template::{readme_benchmarks, readme_stars, Error, TableOptions, Year};
+
+static README_PATH: &str = "README.md";
+
+struct TablePosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    if matches.len() > 2 {
+        return Err(Error::ReadmeMarkers(format!(
+            "too many occurences of marker `{marker}`."
+        )));
+    }
+
+    let pos_start = matches.first().map(|m| m.0).ok_or_else(|| {
+        Error::ReadmeMarkers(format!("could not find table start marker `{marker}`."))
+    })?;
+
+    let pos_end = matches.last().map(|m| m.0 + m.1.len()).ok_or_else(|| {
+        Error::ReadmeMarkers(format!("could not find table end marker `{marker}`."))
+    })?;
+
+    Ok(TablePosition { pos_start, pos_end })
+}
+
+/// Replace the table between the two occurences of `marker` with `table`, which includes the markers.
+pub fn replace_table(s: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
+/// Rewrite the benchmark table of a year, if any timings are stored, and the stars table, if the readme has its markers.
+pub fn update(year: Year, timings: Timings, options: &TableOptions) -> Result<(), Error> {
+    let mut readme = fs::read(README_PATH)
+        .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+        .map_err(|e| Error::io(format!("could not read \"{README_PATH}\""), e))?;
+
+    // without stored timings, the existing table is kept.
+    if !timings.data.is_empty() {
+        let total_millis = timings.total_millis();
+        readme_benchmarks::update_content(&mut readme, year, timings, total_millis, options)?;
+    }
+
+    if readme.contains(readme_stars::MARKER) {
+        readme_stars::update_content(&mut readme, year, &readme_stars::collect(year))?;
+    }
+
+    fs::write(README_PATH, &readme)
+        .map_err(|e| Error::io(format!("could not write \"{README_PATH}\""), e))
+}
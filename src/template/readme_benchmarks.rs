@@ -1,127 +1,283 @@
-/// Module that updates the readme me with timing information.
+/// Module that updates the readme with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashSet, fmt::Display, str::FromStr};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::output::{part_label, PARSE_PART};
+use crate::template::readme::replace_table;
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{Day, Error, PuzzleId, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Width of the bar of the slowest day, in characters.
+const BAR_WIDTH: usize = 20;
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
+/// The order of the days in the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TableOrder {
+    #[default]
+    Day,
+    /// Slowest day first, by the total duration of its parts.
+    Slowest,
+}
+
+impl FromStr for TableOrder {
+    type Err = TableOrderFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(TableOrder::Day),
+            "slowest" => Ok(TableOrder::Slowest),
+            _ => Err(TableOrderFromStrError),
+        }
+    }
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl Display for TableOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableOrder::Day => f.write_str("day"),
+            TableOrder::Slowest => f.write_str("slowest"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`TableOrder`].
+#[derive(Debug)]
+pub struct TableOrderFromStrError;
+
+impl std::error::Error for TableOrderFromStrError {}
+
+impl Display for TableOrderFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a table order of either `day` or `slowest`")
     }
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+/// Presentation options of the benchmark table, see `cargo time --store`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TableOptions {
+    pub order: TableOrder,
+    /// Add a column with the share of each day in the total duration.
+    pub percent: bool,
+    /// Add a column with a bar chart of the total duration of each day.
+    pub bars: bool,
+    /// Print the durations of the `n` slowest parts in bold.
+    pub highlight: usize,
 }
 
+/// Each year has its own table, delimited by a marker that contains the year.
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Draw a bar of `ratio * BAR_WIDTH` characters, using eighth blocks for the fractional part.
+fn bar(ratio: f64) -> String {
+    const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
-    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (ratio.clamp(0.0, 1.0) * (BAR_WIDTH * 8) as f64).round() as usize;
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    let (full, rest) = (eighths / 8, eighths % 8);
+    let mut bar = "█".repeat(full);
+    if rest > 0 {
+        bar.push(EIGHTHS[rest]);
+    }
+    bar
+}
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+/// The `n` slowest parts across all days.
+fn slowest_parts(timings: &[Timing], parts: &[u8], n: usize) -> HashSet<(Day, u8)> {
+    let mut all: Vec<(Day, u8, f64)> = timings
+        .iter()
+        .flat_map(|timing| {
+            parts
+                .iter()
+                .filter_map(|part| Some((timing.day, *part, timing.part_nanos(*part)?)))
+        })
+        .collect();
 
-    Ok(TablePosition { pos_start, pos_end })
+    all.sort_unstable_by(|a, b| b.2.total_cmp(&a.2));
+    all.into_iter()
+        .take(n)
+        .map(|(day, part, _)| (day, part))
+        .collect()
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    mut timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
+    let marker = get_marker(year);
+
+    let any_part = |parts: &[u8], f: fn(&PartTiming) -> bool| {
+        timings
+            .data
+            .iter()
+            .any(|timing| parts.iter().any(|part| timing.part(*part).is_some_and(f)))
+    };
+
+    // the parse column is only shown if some solution has a parse step,
+    // heap columns are only shown if some part was benched with `--alloc`.
+    let parts: Vec<u8> = if any_part(&[PARSE_PART], |_| true) {
+        vec![PARSE_PART, 1, 2]
+    } else {
+        vec![1, 2]
+    };
+    let has_alloc = any_part(&parts, |p| p.alloc.is_some());
+
+    let mut columns: Vec<String> = parts.iter().map(|part| part_label(*part)).collect();
+    if has_alloc {
+        columns.extend(
+            parts
+                .iter()
+                .map(|part| format!("{} Heap", part_label(*part))),
+        );
+    }
+    if options.percent {
+        columns.push("% of Total".into());
+    }
+    let mut alignments = vec![":---:"; columns.len()];
+    // bars are left-aligned so that their lengths can be compared.
+    if options.bars {
+        columns.push("Share".into());
+        alignments.push(":---");
+    }
+
+    if options.order == TableOrder::Slowest {
+        timings
+            .data
+            .sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let total_nanos: f64 = timings.data.iter().map(|timing| timing.total_nanos).sum();
+    let slowest_day_nanos = timings
+        .data
+        .iter()
+        .map(|timing| timing.total_nanos)
+        .fold(0.0, f64::max);
+    let highlighted = slowest_parts(&timings.data, &parts, options.highlight);
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | {} |", columns.join(" | ")),
+        format!("| :---: | {}  |", alignments.join(" | ")),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = PuzzleId::new(year, timing.day).bin_path();
+
+        let mut cells: Vec<String> = parts
+            .iter()
+            .map(|part| {
+                let cell = format!(
+                    "`{}`",
+                    timing
+                        .part(*part)
+                        .map_or_else(|| "-".into(), ToString::to_string)
+                );
+                if highlighted.contains(&(timing.day, *part)) {
+                    format!("**{cell}**")
+                } else {
+                    cell
+                }
+            })
+            .collect();
+
+        if has_alloc {
+            cells.extend(parts.iter().map(|part| {
+                let alloc = timing
+                    .part(*part)
+                    .and_then(|p| p.alloc)
+                    .map_or_else(|| "-".into(), |alloc| alloc.to_string());
+                format!("`{alloc}`")
+            }));
+        }
+
+        if options.percent {
+            let percent = if total_nanos > 0.0 {
+                timing.total_nanos / total_nanos * 100.0
+            } else {
+                0.0
+            };
+            cells.push(format!("`{percent:.1}%`"));
+        }
+
+        if options.bars {
+            let ratio = if slowest_day_nanos > 0.0 {
+                timing.total_nanos / slowest_day_nanos
+            } else {
+                0.0
+            };
+            cells.push(bar(ratio));
+        }
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cells.join(" | ")
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
-}
-
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+pub fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
+    let table = construct_table("##", year, timings, total_millis, options);
+    replace_table(s, &get_marker(year), &table)
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{get_marker, update_content, TableOptions, TableOrder};
+    use crate::{
+        day,
+        template::{
+            heap::AllocStats,
+            timings::{tests::part, Timing, Timings},
+        },
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    part_1: part(10e6),
+                    part_2: part(20e6),
+                    total_nanos: 3e7,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: None,
+                    part_1: part(30e6),
+                    part_2: part(40e6),
+                    total_nanos: 7e7,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    parse: None,
+                    part_1: part(40e6),
+                    part_2: part(50e6),
+                    total_nanos: 9e7,
                 },
             ],
         }
@@ -131,53 +287,183 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = get_marker(year!(2024));
+        let mut s = format!("{} {} {}", marker, marker, marker);
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker, marker);
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2024 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker, marker);
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(s.matches(&marker).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(
+            s.matches("## 2024 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", marker, marker);
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2024 --->",
+            "## 2024 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024_01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024_02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024_04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2024 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_alloc_stats() {
+        let marker = get_marker(year!(2024));
+        let mut timings = get_mock_timings();
+        timings.data.truncate(1);
+        timings.data[0].part_1.as_mut().unwrap().alloc = Some(AllocStats {
+            allocations: 1000,
+            bytes: 2 * 1024 * 1024,
+            peak_bytes: 512 * 1024,
+        });
+
+        let mut s = format!("{}\n{}", marker, marker);
+        update_content(&mut s, year!(2024), timings, 30.0, &TableOptions::default()).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "| Day | Part 1 | Part 2 | Part 1 Heap | Part 2 Heap |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/2024_01.rs) | `10.0ms` | `20.0ms` | `1000 allocs, 2.0 MiB, peak 512.0 KiB` | `-` |"
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_step() {
+        let marker = get_marker(year!(2024));
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = part(5e6);
+
+        let mut s = format!("{}\n{}", marker, marker);
+        update_content(
+            &mut s,
+            year!(2024),
+            timings,
+            195.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Parse | Part 1 | Part 2 |");
+        assert_eq!(lines[4], "| :---: | :---: | :---: | :---:  |");
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/2024_01.rs) | `-` | `10.0ms` | `20.0ms` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/2024_02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_options() {
+        let marker = get_marker(year!(2024));
+        let options = TableOptions {
+            order: TableOrder::Slowest,
+            percent: true,
+            bars: true,
+            highlight: 3,
+        };
+
+        let mut s = format!("{}\n{}", marker, marker);
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, &options).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Part 1 | Part 2 | % of Total | Share |");
+        assert_eq!(lines[4], "| :---: | :---: | :---: | :---: | :---  |");
+        assert_eq!(
+            lines[5],
+            "| [Day 4](./src/bin/2024_04.rs) | **`40.0ms`** | **`50.0ms`** | `47.4%` | ████████████████████ |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/2024_02.rs) | `30.0ms` | **`40.0ms`** | `36.8%` | ███████████████▌ |"
+        );
+        assert_eq!(
+            lines[7],
+            "| [Day 1](./src/bin/2024_01.rs) | `10.0ms` | `20.0ms` | `15.8%` | ██████▋ |"
+        );
+    }
 }
@@ -0,0 +1,148 @@
+/// Module that updates the readme with the stars collected so far, replacing the `advent-readme-stars` workflow.
+/// Parts count as solved if their answer is known, see `cargo answer`, or was accepted on submission.
+use std::fs;
+
+use crate::template::ledger::Ledger;
+use crate::template::readme::replace_table;
+use crate::template::{all_days, answers, aoc_client, Day, Error, PuzzleId, Year};
+
+/// The marker used by `advent-readme-stars`, so that existing readmes keep working.
+pub const MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Solved parts and title of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    /// The title of the puzzle, if it was downloaded, e.g. `Day 1: Historian Hysteria`.
+    pub title: Option<String>,
+    pub parts: [bool; 2],
+}
+
+/// Collect the stars of a year from the stored answers and the submission ledger.
+pub fn collect(year: Year) -> Vec<DayStars> {
+    let ledger = Ledger::read_from_file().unwrap_or_else(|e| {
+        eprintln!("failed to read submission ledger: {e}");
+        Ledger::default()
+    });
+
+    let mut stars: Vec<DayStars> = all_days()
+        .map(|day| {
+            let puzzle = PuzzleId::new(year, day);
+            let is_solved = |part: u8| {
+                answers::read(puzzle, part).is_some()
+                    || ledger.correct_answer(puzzle, part).is_some()
+            };
+
+            DayStars {
+                day,
+                title: fs::read_to_string(puzzle.puzzle_path())
+                    .ok()
+                    .and_then(|markdown| aoc_client::puzzle_title(&markdown)),
+                parts: [is_solved(1), is_solved(2)],
+            }
+        })
+        .collect();
+
+    award_last_star(&mut stars);
+    stars
+}
+
+/// The second part of the last day has no answer, its star is awarded for all other stars.
+fn award_last_star(stars: &mut [DayStars]) {
+    if let Some((last, rest)) = stars.split_last_mut() {
+        let has_all_others = last.parts[0] && rest.iter().all(|day| day.parts == [true, true]);
+        if rest.len() == 24 && has_all_others {
+            last.parts[1] = true;
+        }
+    }
+}
+
+fn construct_table(year: Year, stars: &[DayStars]) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in stars.iter().filter(|day| day.parts.contains(&true)) {
+        let title = day
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Day {}", day.day.into_inner()));
+        let [part_1, part_2] = day.parts.map(|solved| if solved { "⭐" } else { " " });
+
+        lines.push(format!(
+            "| [{title}](https://adventofcode.com/{year}/day/{}) | {part_1} | {part_2} |",
+            day.day.into_inner()
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+pub fn update_content(s: &mut String, year: Year, stars: &[DayStars]) -> Result<(), Error> {
+    replace_table(s, MARKER, &construct_table(year, stars))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{award_last_star, update_content, DayStars, MARKER};
+    use crate::{
+        day,
+        template::{all_days, Day},
+        year,
+    };
+
+    fn stars(day: Day, parts: [bool; 2]) -> DayStars {
+        DayStars {
+            day,
+            title: None,
+            parts,
+        }
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\nold\n{MARKER}\nbar");
+        let stars = vec![
+            DayStars {
+                title: Some("Day 1: Historian Hysteria".into()),
+                ..stars(day!(1), [true, true])
+            },
+            stars(day!(2), [false, false]),
+            stars(day!(3), [true, false]),
+        ];
+
+        update_content(&mut s, year!(2024), &stars).unwrap();
+        let expected = [
+            "foo",
+            MARKER,
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1: Historian Hysteria](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | ⭐ |   |",
+            MARKER,
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn awards_last_star_for_all_others() {
+        let mut all: Vec<DayStars> = all_days().map(|day| stars(day, [true, true])).collect();
+        all[24].parts = [true, false];
+        award_last_star(&mut all);
+        assert_eq!(all[24].parts, [true, true]);
+
+        all[24].parts = [true, false];
+        all[3].parts = [true, false];
+        award_last_star(&mut all);
+        assert_eq!(all[24].parts, [true, false]);
+    }
+}
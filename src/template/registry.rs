@@ -0,0 +1,120 @@
+/// Registry of solutions that are linked into the single runner binary `src/bin/runner.rs`.
+/// The `solution!` macro registers the parts of every day, `build.rs` collects them into the runner.
+use std::{fs, panic, time::Instant};
+
+use crate::template::output::{PartResult, PartStatus, RunnerEvent};
+use crate::template::{parallel, runner, Day, Error, PuzzleId, Year};
+
+/// Runs a single part against an input.
+pub type PartFn = fn(&str) -> PartResult;
+
+/// The registered parts of a solution, with their part number.
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub parts: &'static [(u8, PartFn)],
+}
+
+impl Solution {
+    /// The event announcing the parts of this solution, see [`RunnerEvent::Begin`].
+    pub fn begin_event(&self) -> RunnerEvent {
+        RunnerEvent::Begin {
+            day: self.puzzle.day,
+            parts: self.parts.iter().map(|(part, _)| *part).collect(),
+        }
+    }
+
+    /// Run all parts against the puzzle input. Returns no results if the input is missing.
+    /// A part that panics is reported as crashed, so that the remaining solutions keep running.
+    ///
+    /// With `events`, every result is printed as soon as its part finished, so that it is not lost when a later part hangs.
+    pub fn run(&self, events: bool) -> Vec<PartResult> {
+        let path = self.puzzle.input_path();
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("could not open input file \"{path}\": {e}");
+                return vec![];
+            }
+        };
+
+        if events {
+            println!("{}", self.begin_event().to_json_line());
+        }
+
+        self.parts
+            .iter()
+            .map(|(part, func)| {
+                if events {
+                    let event = RunnerEvent::Start {
+                        day: self.puzzle.day,
+                        part: *part,
+                    };
+                    println!("{}", event.to_json_line());
+                }
+
+                let timer = Instant::now();
+                let result = panic::catch_unwind(|| func(&input)).unwrap_or_else(|_| {
+                    let nanos = timer.elapsed().as_secs_f64() * 1e9;
+                    PartResult::failed(self.puzzle.day, *part, PartStatus::Crashed, nanos)
+                });
+
+                if events {
+                    println!("{}", result.to_json_line());
+                }
+
+                result
+            })
+            .collect()
+    }
+}
+
+/// Entry point of the runner binary.
+///
+/// Runs every registered solution of the year passed with `--year`, optionally limited to the days passed with `--day`.
+/// Results are printed as one JSON record per line, in the order of `solutions`.
+/// With `--jobs <n>`, up to `n` solutions run at the same time. The `--time` flag is read by each part.
+/// With `--events`, the parts of each day, the start of each part and the end of each day are reported as [`RunnerEvent`]s.
+pub fn run(solutions: &[Solution]) {
+    let mut args = pico_args::Arguments::from_env();
+
+    let (year, days, jobs) = match parse_args(&mut args) {
+        Ok(parsed) => parsed,
+        Err(e) => runner::exit_with_error(&Error::Usage(e.to_string())),
+    };
+
+    let selected: Vec<&Solution> = solutions
+        .iter()
+        .filter(|solution| year.is_none_or(|year| solution.puzzle.year == year))
+        .filter(|solution| days.is_empty() || days.contains(&solution.puzzle.day))
+        .collect();
+
+    let events = runner::has_events();
+
+    parallel::run_ordered(
+        &selected,
+        jobs,
+        |solution| solution.run(events),
+        |solution, results| {
+            // with events, results were already printed by `Solution::run`.
+            if events {
+                let event = RunnerEvent::Done {
+                    day: solution.puzzle.day,
+                };
+                println!("{}", event.to_json_line());
+            } else {
+                results
+                    .iter()
+                    .for_each(|result| println!("{}", result.to_json_line()));
+            }
+        },
+    );
+}
+
+fn parse_args(
+    args: &mut pico_args::Arguments,
+) -> Result<(Option<Year>, Vec<Day>, usize), pico_args::Error> {
+    let year = args.opt_value_from_str("--year")?;
+    let days = args.values_from_str("--day")?;
+    let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+    Ok((year, days, jobs))
+}
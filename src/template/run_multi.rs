@@ -1,257 +1,1112 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    output::{part_label, PartResult},
+    runner::{format_failure, print_part_result},
+    Day, Error, OutputFormat, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
 
 use super::{
-    all_days,
+    all_days, parallel,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// How solutions are executed by [`run_multi`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Execution {
+    /// All days run in a single process of the runner binary.
+    #[default]
+    Registry,
+    /// Every day runs in its own solution binary.
+    Isolated,
+}
+
+/// Wall-clock limits for solutions run by [`run_multi`]. A solution that exceeds one is killed and reported as timed out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// The limit for all parts of a day.
+    pub day: Option<Duration>,
+    /// The limit for a single part.
+    pub part: Option<Duration>,
+}
+
+impl Timeouts {
+    /// The timeouts of `cargo all`, which keep a hanging day from blocking the run.
+    pub const ALL_DEFAULT: Timeouts = Timeouts {
+        day: Some(Duration::from_secs(120)),
+        part: Some(Duration::from_secs(60)),
+    };
+
+    /// The point in time at which a day is killed, given when it and its current part started.
+    fn deadline(&self, day_started: Instant, part_started: Option<Instant>) -> Option<Instant> {
+        let day = self.day.map(|limit| day_started + limit);
+        let part = self
+            .part
+            .zip(part_started)
+            .map(|(limit, start)| start + limit);
+        day.into_iter().chain(part).min()
+    }
+}
+
+/// Options of [`run_multi`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub format: OutputFormat,
+    pub execution: Execution,
+    /// Untimed runs execute up to this many days at the same time.
+    pub jobs: usize,
+    pub timeouts: Timeouts,
+    /// Build solutions with the `alloc-stats` feature, reporting the heap usage of each part.
+    pub alloc_stats: bool,
+}
+
+/// The outcome of [`run_multi`].
+pub struct RunSummary {
+    /// Timings of all solved days, only present for timed runs.
+    pub timings: Option<Timings>,
+    /// Parts whose answer differs from their known answer.
+    pub wrong_answers: Vec<PartResult>,
+    /// Parts that timed out or crashed.
+    pub failures: Vec<PartResult>,
+}
+
+impl RunSummary {
+    /// Print the parts whose answer changed to stderr.
+    pub fn print_wrong_answers(&self) {
+        if self.wrong_answers.is_empty() {
+            return;
+        }
+
+        eprintln!();
+        eprintln!(
+            "{ANSI_BOLD}{} known answer(s) changed:{ANSI_RESET}",
+            self.wrong_answers.len()
+        );
+        for result in &self.wrong_answers {
+            eprintln!(
+                "{ANSI_RED}Day {} {}: {}, {}{ANSI_RESET}",
+                result.day,
+                part_label(result.part),
+                result.answer.as_deref().unwrap_or("no answer"),
+                result.check
+            );
+        }
+    }
+
+    /// Print the parts that timed out or crashed to stderr.
+    pub fn print_failures(&self) {
+        if self.failures.is_empty() {
+            return;
+        }
+
+        eprintln!();
+        eprintln!(
+            "{ANSI_BOLD}{} part(s) did not finish:{ANSI_RESET}",
+            self.failures.len()
+        );
+        for result in &self.failures {
+            eprintln!(
+                "{ANSI_RED}Day {} {}: {}{ANSI_RESET}",
+                result.day,
+                part_label(result.part),
+                format_failure(result)
+            );
+        }
+    }
+}
 
-    let mut need_space = false;
+/// Run the solutions of the given days, printing the results of each day in ascending order.
+///
+/// Untimed runs execute up to `jobs` days at the same time. Timed runs are always serial so that they don't disturb each other's measurements.
+/// A day that exceeds a timeout or crashes is recorded as such and the remaining days keep running.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    options: &RunOptions,
+) -> Result<RunSummary, Error> {
+    let options = RunOptions {
+        jobs: if options.is_timed {
+            1
+        } else {
+            options.jobs.max(1)
+        },
+        ..*options
+    };
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut report = Report::new(options.format, days.clone());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    match options.execution {
+        Execution::Registry => {
+            let puzzles: Vec<PuzzleId> = days.iter().map(|day| PuzzleId::new(year, *day)).collect();
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            child_commands::run_registry(year, &puzzles, &options, |result| {
+                report.push(result);
+            })?;
+        }
+        Execution::Isolated if options.jobs > 1 => {
+            let mut error = None;
+            parallel::run_ordered(
+                &days,
+                options.jobs,
+                |day| {
+                    let mut results = vec![];
+                    child_commands::run_solution(PuzzleId::new(year, *day), &options, |result| {
+                        results.push(result);
+                    })
+                    .map(|()| results)
+                },
+                |day, results| match results {
+                    Ok(results) => {
+                        report.start_day(*day);
+                        results.into_iter().for_each(|result| report.push(result));
+                    }
+                    Err(e) => {
+                        error.get_or_insert(e);
+                    }
+                },
+            );
+            if let Some(e) = error {
+                return Err(e);
             }
-        });
+        }
+        Execution::Isolated => {
+            for day in &days {
+                report.start_day(*day);
+                child_commands::run_solution(PuzzleId::new(year, *day), &options, |result| {
+                    report.push(result);
+                })?;
+            }
+        }
+    }
 
-    if is_timed {
+    let (timings, wrong_answers, failures) = report.finish();
+
+    let timings = options.is_timed.then(|| {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
-    }
+        if options.format == OutputFormat::Text {
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+        timings
+    });
+
+    Ok(RunSummary {
+        timings,
+        wrong_answers,
+        failures,
+    })
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+/// Prints results as they arrive, grouped by day in ascending order, and collects their timings.
+struct Report {
+    format: OutputFormat,
+    /// Days that have not been started yet.
+    pending: std::vec::IntoIter<Day>,
+    current: Option<(Day, Vec<PartResult>)>,
+    timings: Vec<Timing>,
+    wrong_answers: Vec<PartResult>,
+    failures: Vec<PartResult>,
+    need_space: bool,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl Report {
+    fn new(format: OutputFormat, days: Vec<Day>) -> Self {
+        Report {
+            format,
+            pending: days.into_iter(),
+            current: None,
+            timings: vec![],
+            wrong_answers: vec![],
+            failures: vec![],
+            need_space: false,
+        }
     }
-}
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    /// Finish the current day and start all days up to and including `day`.
+    fn start_day(&mut self, day: Day) {
+        if self.current.as_ref().is_some_and(|(d, _)| *d == day) {
+            return;
+        }
+
+        self.finish_day();
+
+        while let Some(next) = self.pending.next() {
+            if self.format == OutputFormat::Text {
+                if self.need_space {
+                    println!();
+                }
+                self.need_space = true;
+
+                println!("{ANSI_BOLD}Day {next}{ANSI_RESET}");
+                println!("------");
+            }
+
+            if next == day {
+                self.current = Some((day, vec![]));
+                break;
+            }
+
+            // days without results in between were not solved.
+            self.current = Some((next, vec![]));
+            self.finish_day();
+        }
+    }
+
+    fn push(&mut self, result: PartResult) {
+        self.start_day(result.day);
+
+        match self.format {
+            OutputFormat::Text => print_part_result(&result),
+            OutputFormat::Json => println!("{}", result.to_json_line()),
+        }
+
+        if result.is_failure() {
+            self.failures.push(result.clone());
+        } else if result.check.is_wrong() {
+            self.wrong_answers.push(result.clone());
+        }
+
+        if let Some((_, results)) = &mut self.current {
+            results.push(result);
+        }
+    }
+
+    fn finish_day(&mut self) {
+        let Some((day, results)) = self.current.take() else {
+            return;
+        };
+
+        if results.is_empty() {
+            if self.format == OutputFormat::Text {
+                println!("Not solved.");
+            }
+        } else {
+            self.timings.push(Timing::from_results(day, &results));
+        }
+    }
+
+    fn finish(mut self) -> (Vec<Timing>, Vec<PartResult>, Vec<PartResult>) {
+        if let Some(last) = self.pending.as_slice().last().copied() {
+            self.start_day(last);
+        }
+        self.finish_day();
+        (self.timings, self.wrong_answers, self.failures)
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// Solutions live in isolated binaries and are linked together into the runner binary.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their structured results.
+///
+/// Children are started with `--events` and report the parts of every day and the start of every part, so that they can be killed once a part or day exceeds its timeout.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{RunOptions, Timeouts};
+    use crate::template::{
+        output::{part_label, PartResult, PartStatus, RunnerEvent},
+        Day, Error, OutputFormat, PuzzleId, Year, ANSI_CLEAR_LINE, ANSI_ITALIC, ANSI_RED,
+        ANSI_RESET,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::{BTreeMap, HashMap},
+        io::{self, BufRead, BufReader, IsTerminal},
+        path::{Path, PathBuf},
+        process::{Command, ExitStatus, Stdio},
+        str::FromStr,
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::Instant,
     };
+    use tinyjson::JsonValue;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle, passing each solved part to `on_result`.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        options: &RunOptions,
+        mut on_result: impl FnMut(PartResult),
+    ) -> Result<(), Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(());
+        }
+
+        run_bin(&puzzle.bin_name(), &[], options, &mut |_, results| {
+            results.into_iter().for_each(&mut on_result);
+        })
+        .map(|_| ())
+    }
+
+    /// Run all given puzzles in a single process of the runner binary, passing each solved part to `on_result`.
+    ///
+    /// When a day is killed, all other days in flight are lost with the process.
+    /// The runner is started again for the days that did not finish yet, until every day finished or failed.
+    pub fn run_registry(
+        year: Year,
+        puzzles: &[PuzzleId],
+        options: &RunOptions,
+        on_result: impl FnMut(PartResult),
+    ) -> Result<(), Error> {
+        // skip days that have not been scaffolded yet.
+        let days: Vec<Day> = puzzles
+            .iter()
+            .filter(|puzzle| Path::new(&puzzle.bin_path()).exists())
+            .map(|puzzle| puzzle.day)
+            .collect();
+
+        restart_until_done(
+            &days,
+            |remaining, on_day| {
+                let mut args = vec![
+                    "--year".to_string(),
+                    year.to_string(),
+                    "--jobs".to_string(),
+                    options.jobs.to_string(),
+                ];
+                for day in remaining {
+                    args.extend(["--day".to_string(), day.to_string()]);
+                }
+
+                run_bin("runner", &args, options, on_day)
+            },
+            on_result,
+        )
+    }
+
+    /// Run passes over the days that did not finish yet, passing their results to `on_result` in ascending order of days.
+    /// A pass that makes no progress, e.g. because the runner failed to build, records all remaining days as crashed.
+    pub(super) fn restart_until_done(
+        days: &[Day],
+        mut run_pass: impl FnMut(
+            &[Day],
+            &mut dyn FnMut(Day, Vec<PartResult>),
+        ) -> Result<ExitStatus, Error>,
+        mut on_result: impl FnMut(PartResult),
+    ) -> Result<(), Error> {
+        let mut order = InOrder::new(days);
+        let mut remaining = days.to_vec();
+
+        while !remaining.is_empty() {
+            let finished_before = order.finished.len();
+
+            let status = run_pass(&remaining, &mut |day, results| {
+                order.insert(day, results, &mut on_result);
+            })?;
+
+            if order.finished.len() == finished_before {
+                eprintln!(
+                    "{ANSI_RED}The runner exited with {status} before finishing {} day(s).{ANSI_RESET}",
+                    remaining.len()
+                );
+                // no day began, so their parts are unknown and the failure is recorded against part 1.
+                for day in remaining {
+                    let results = vec![PartResult::failed(day, 1, PartStatus::Crashed, 0.0)];
+                    order.insert(day, results, &mut on_result);
+                }
+                break;
+            }
+
+            remaining.retain(|day| !order.finished.contains_key(day));
+        }
+
+        Ok(())
+    }
+
+    /// Passes on the results of days in ascending order, as days can finish or fail out of order.
+    struct InOrder {
+        days: Vec<Day>,
+        next: usize,
+        finished: BTreeMap<Day, Vec<PartResult>>,
+    }
+
+    impl InOrder {
+        fn new(days: &[Day]) -> Self {
+            InOrder {
+                days: days.to_vec(),
+                next: 0,
+                finished: BTreeMap::new(),
+            }
+        }
+
+        fn insert(
+            &mut self,
+            day: Day,
+            results: Vec<PartResult>,
+            on_result: &mut impl FnMut(PartResult),
+        ) {
+            self.finished.insert(day, results);
+
+            while let Some(results) = self
+                .days
+                .get(self.next)
+                .and_then(|day| self.finished.get_mut(day))
+            {
+                results.drain(..).for_each(&mut *on_result);
+                self.next += 1;
+            }
+        }
+    }
+
+    /// A day that has started in a child process but has not finished yet.
+    struct DayRun {
+        started: Instant,
+        /// The parts of the solution, as announced by the child.
+        parts: Vec<u8>,
+        part: Option<(u8, Instant)>,
+        results: Vec<PartResult>,
+    }
+
+    impl DayRun {
+        fn new(started: Instant) -> Self {
+            DayRun {
+                started,
+                parts: vec![],
+                part: None,
+                results: vec![],
+            }
+        }
+
+        /// The first announced part of the solution that did not report a result yet.
+        fn next_part(&self) -> Option<u8> {
+            self.parts
+                .iter()
+                .copied()
+                .find(|part| !self.results.iter().any(|result| result.part == *part))
+        }
+
+        /// Whether all announced parts reported, so that the day only waits for its turn to be done.
+        fn is_complete(&self) -> bool {
+            self.part.is_none() && !self.parts.is_empty() && self.next_part().is_none()
+        }
+
+        /// The results of this day, with the part in flight, or else the next part, recorded as failed at `now`.
+        /// A day without a pending part keeps its results as they are.
+        fn fail(mut self, day: Day, status: PartStatus, now: Instant) -> Vec<PartResult> {
+            let Some((part, since)) = self
+                .part
+                .or_else(|| self.next_part().map(|part| (part, self.started)))
+            else {
+                return self.results;
+            };
+            let nanos = now.saturating_duration_since(since).as_secs_f64() * 1e9;
+            self.results
+                .push(PartResult::failed(day, part, status, nanos));
+            self.results
+        }
+    }
+
+    /// What a line of a child's stdout meant to [`DayTracker`].
+    pub(super) enum Tracked {
+        /// A part started.
+        Started(Day, u8),
+        /// A day announced its parts or a part finished.
+        Progress,
+        /// All results of a day were reported.
+        Done(Day, Vec<PartResult>),
+        /// Any other output of the solution.
+        Output,
+    }
+
+    /// Tracks the days in flight in a child process from its events and results, and their deadlines.
+    pub(super) struct DayTracker {
+        timeouts: Timeouts,
+        days: BTreeMap<Day, DayRun>,
+    }
+
+    impl DayTracker {
+        pub(super) fn new(timeouts: Timeouts) -> Self {
+            DayTracker {
+                timeouts,
+                days: BTreeMap::new(),
+            }
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        /// Track a line of the child's stdout, received at `now`.
+        pub(super) fn track(&mut self, line: &str, now: Instant) -> Tracked {
+            if let Ok(event) = line.parse::<RunnerEvent>() {
+                match event {
+                    RunnerEvent::Begin { day, parts } => {
+                        let run = self.days.entry(day).or_insert_with(|| DayRun::new(now));
+                        run.parts = parts;
+                        Tracked::Progress
+                    }
+                    RunnerEvent::Start { day, part } => {
+                        let run = self.days.entry(day).or_insert_with(|| DayRun::new(now));
+                        run.part = Some((part, now));
+                        Tracked::Started(day, part)
+                    }
+                    RunnerEvent::Done { day } => {
+                        let run = self.days.remove(&day).unwrap_or_else(|| DayRun::new(now));
+                        Tracked::Done(day, run.results)
+                    }
+                }
+            } else if let Ok(result) = line.parse::<PartResult>() {
+                let run = self
+                    .days
+                    .entry(result.day)
+                    .or_insert_with(|| DayRun::new(now));
+                run.part = None;
+                run.results.push(result);
+                Tracked::Progress
+            } else {
+                Tracked::Output
+            }
+        }
+
+        fn deadline(&self, run: &DayRun) -> Option<Instant> {
+            if run.is_complete() {
+                return None;
+            }
+            self.timeouts
+                .deadline(run.started, run.part.map(|(_, start)| start))
+        }
+
+        /// The earliest point in time at which a day in flight exceeds a timeout.
+        pub(super) fn next_deadline(&self) -> Option<Instant> {
+            self.days
+                .values()
+                .filter_map(|run| self.deadline(run))
+                .min()
+        }
+
+        /// Remove the days that exceeded a timeout at `now`, with the part in flight recorded as timed out.
+        pub(super) fn expire(&mut self, now: Instant) -> Vec<(Day, Vec<PartResult>)> {
+            let expired: Vec<Day> = self
+                .days
+                .iter()
+                .filter(|(_, run)| self.deadline(run).is_some_and(|at| at <= now))
+                .map(|(day, _)| *day)
+                .collect();
+
+            expired
+                .into_iter()
+                .filter_map(|day| {
+                    let run = self.days.remove(&day)?;
+                    Some((day, run.fail(day, PartStatus::TimedOut, now)))
+                })
+                .collect()
+        }
+
+        /// The days that did not report to be done when the child exited. They either ended with it or crashed it.
+        pub(super) fn finish(self, success: bool, now: Instant) -> Vec<(Day, Vec<PartResult>)> {
+            self.days
+                .into_iter()
+                .map(|(day, run)| {
+                    if success {
+                        (day, run.results)
+                    } else {
+                        (day, run.fail(day, PartStatus::Crashed, now))
+                    }
+                })
+                .collect()
+        }
+    }
+
+    /// Build a binary, returning the exit status of cargo and the path of the executable if the build succeeded.
+    fn build_bin(bin_name: &str, options: &RunOptions) -> Result<(ExitStatus, Option<PathBuf>), Error> {
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+            "--bin",
+            bin_name,
+        ];
 
-        if is_release {
+        if options.is_release {
             args.push("--release");
         }
 
-        if is_timed {
+        if options.alloc_stats {
+            args.extend(["--features", "alloc-stats"]);
+        }
+
+        let command = format!("cargo {}", args.join(" "));
+
+        // diagnostics are rendered to stderr, stdout only carries the JSON messages of cargo.
+        let output = Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|source| Error::CargoSpawn {
+                command: command.clone(),
+                source,
+            })?;
+
+        if !output.status.success() {
+            return Ok((output.status, None));
+        }
+
+        let executable = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| artifact_executable(line, bin_name))
+            .ok_or_else(|| {
+                Error::io(
+                    format!("`{command}` did not report the executable of `{bin_name}`"),
+                    io::ErrorKind::NotFound.into(),
+                )
+            })?;
+
+        Ok((output.status, Some(executable)))
+    }
+
+    /// The executable of `bin_name`, if `line` is the cargo message of its build artifact.
+    pub(super) fn artifact_executable(line: &str, bin_name: &str) -> Option<PathBuf> {
+        let json = JsonValue::from_str(line).ok()?;
+        let message = json.get::<HashMap<String, JsonValue>>()?;
+
+        let is_artifact = message.get("reason")?.get::<String>()? == "compiler-artifact";
+        let name = message
+            .get("target")?
+            .get::<HashMap<String, JsonValue>>()?
+            .get("name")?
+            .get::<String>()?;
+
+        if !is_artifact || name != bin_name {
+            return None;
+        }
+
+        message
+            .get("executable")?
+            .get::<String>()
+            .map(PathBuf::from)
+    }
+
+    /// Shows the part in flight of a timed run in text format on the terminal, like a single day shows that it is benching.
+    /// The hint is printed to stderr and cleared once the part reported, so that it does not end up in the results.
+    struct BenchingHint {
+        enabled: bool,
+        shown: bool,
+    }
+
+    impl BenchingHint {
+        fn new(options: &RunOptions) -> Self {
+            BenchingHint {
+                enabled: options.is_timed
+                    && options.format == OutputFormat::Text
+                    && io::stderr().is_terminal(),
+                shown: false,
+            }
+        }
+
+        fn show(&mut self, day: Day, part: u8) {
+            if self.enabled {
+                self.clear();
+                eprint!(
+                    "Day {day} {}: > {ANSI_ITALIC}benching{ANSI_RESET}",
+                    part_label(part)
+                );
+                self.shown = true;
+            }
+        }
+
+        fn clear(&mut self) {
+            if self.shown {
+                eprint!("{ANSI_CLEAR_LINE}");
+                self.shown = false;
+            }
+        }
+    }
+
+    /// Run a binary, passing the results of each day to `on_day` once it has finished, timed out or crashed.
+    /// Returns the exit status of the binary, or of cargo if the binary failed to build.
+    ///
+    /// The binary is built first and then started directly, so that a day that timed out is killed along with its process.
+    fn run_bin(
+        bin_name: &str,
+        extra_args: &[String],
+        options: &RunOptions,
+        on_day: &mut dyn FnMut(Day, Vec<PartResult>),
+    ) -> Result<ExitStatus, Error> {
+        let (status, executable) = build_bin(bin_name, options)?;
+        let Some(executable) = executable else {
+            return Ok(status);
+        };
+
+        // children always report in JSON, output is re-rendered in the requested format.
+        let mut args = vec!["--format", "json", "--events"];
+        args.extend(extra_args.iter().map(String::as_str));
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr while collecting result records from stdout.
+
+        let command = format!("{} {}", executable.display(), args.join(" "));
+        let output_error =
+            |source| Error::io(format!("could not read output of `{command}`"), source);
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(&executable)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
+            .spawn()
+            .map_err(|source| Error::CargoSpawn {
+                command: command.clone(),
+                source,
+            })?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let broken_pipe = || output_error(io::ErrorKind::BrokenPipe.into());
+        let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
 
-        let mut output = vec![];
+        let stderr_thread = thread::spawn(move || {
+            stderr
+                .lines()
+                .map_while(Result::ok)
+                .for_each(|line| eprintln!("{line}"));
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        // stdout is read on its own thread, so that waiting for a line can time out.
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let mut tracker = DayTracker::new(options.timeouts);
+        let mut hint = BenchingHint::new(options);
+
+        loop {
+            let line = match tracker.next_deadline() {
+                Some(next_deadline) => {
+                    receiver.recv_timeout(next_deadline.saturating_duration_since(Instant::now()))
+                }
+                None => receiver.recv().map_err(RecvTimeoutError::from),
+            };
+
+            match line {
+                Ok(line) => {
+                    let line = line.map_err(output_error)?;
+                    hint.clear();
+                    match tracker.track(&line, Instant::now()) {
+                        Tracked::Started(day, part) => hint.show(day, part),
+                        Tracked::Progress => {}
+                        Tracked::Done(day, results) => on_day(day, results),
+                        // any other output of the solution is forwarded as is, keeping stdout parseable in JSON mode.
+                        Tracked::Output => match options.format {
+                            OutputFormat::Text => println!("{line}"),
+                            OutputFormat::Json => eprintln!("{line}"),
+                        },
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let expired = tracker.expire(Instant::now());
+                    if expired.is_empty() {
+                        continue;
+                    }
+
+                    hint.clear();
+
+                    // NOTE: the stderr of a killed child is not awaited, it may be held open by its own children.
+                    cmd.kill().map_err(output_error)?;
+                    let status = cmd.wait().map_err(output_error)?;
+
+                    for (day, results) in expired {
+                        on_day(day, results);
+                    }
+
+                    return Ok(status);
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        hint.clear();
+
+        let _ = stderr_thread.join();
+        let status = cmd.wait().map_err(output_error)?;
+
+        for (day, results) in tracker.finish(status.success(), Instant::now()) {
+            on_day(day, results);
+        }
 
-        Ok(output)
+        Ok(status)
     }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        path::PathBuf,
+        process::ExitStatus,
+        time::{Duration, Instant},
+    };
+
+    use super::{
+        child_commands::{artifact_executable, restart_until_done, DayTracker, Tracked},
+        Timeouts,
+    };
+    use crate::{
+        day,
+        template::{
+            output::{PartResult, PartStatus, RunnerEvent},
+            Day,
+        },
+    };
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
+    fn begin(day: Day, parts: &[u8]) -> String {
+        RunnerEvent::Begin {
             day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+            parts: parts.to_vec(),
+        }
+        .to_json_line()
+    }
+
+    fn start(day: Day, part: u8) -> String {
+        RunnerEvent::Start { day, part }.to_json_line()
+    }
+
+    fn done(day: Day) -> String {
+        RunnerEvent::Done { day }.to_json_line()
+    }
+
+    fn finished(day: Day, part: u8) -> String {
+        PartResult::failed(day, part, PartStatus::Finished, 1000.0).to_json_line()
+    }
+
+    fn statuses(results: &[PartResult]) -> Vec<(Day, u8, PartStatus)> {
+        results
+            .iter()
+            .map(|result| (result.day, result.part, result.status))
+            .collect()
+    }
+
+    #[test]
+    fn deadline_is_the_earliest_limit() {
+        let timeouts = Timeouts {
+            day: Some(Duration::from_secs(10)),
+            part: Some(Duration::from_secs(3)),
+        };
+        let day_started = Instant::now();
+        let part_started = day_started + Duration::from_secs(8);
+
+        assert_eq!(
+            timeouts.deadline(day_started, None),
+            Some(day_started + Duration::from_secs(10))
+        );
+        assert_eq!(
+            timeouts.deadline(day_started, Some(day_started)),
+            Some(day_started + Duration::from_secs(3))
+        );
+        assert_eq!(
+            timeouts.deadline(day_started, Some(part_started)),
+            Some(day_started + Duration::from_secs(10))
+        );
+        assert_eq!(
+            Timeouts::default().deadline(day_started, Some(part_started)),
+            None
+        );
+    }
+
+    #[test]
+    fn tracks_timed_out_and_crashed_days() {
+        let mut tracker = DayTracker::new(Timeouts {
+            day: None,
+            part: Some(Duration::from_secs(1)),
+        });
+        let t0 = Instant::now();
+
+        tracker.track(&start(day!(1), 1), t0);
+        tracker.track(&finished(day!(1), 1), t0);
+        tracker.track(&start(day!(1), 2), t0);
+        tracker.track(&start(day!(3), 1), t0);
+        tracker.track(&finished(day!(3), 1), t0);
+        assert!(matches!(tracker.track("debug output", t0), Tracked::Output));
+
+        let Tracked::Done(day, results) = tracker.track(&done(day!(3)), t0) else {
+            panic!("day 3 should be done");
         };
+        assert_eq!(day, day!(3));
+        assert_eq!(statuses(&results), vec![(day!(3), 1, PartStatus::Finished)]);
 
-        output
+        let day_2_started = t0 + Duration::from_millis(900);
+        tracker.track(&start(day!(2), 1), day_2_started);
+        assert_eq!(tracker.next_deadline(), Some(t0 + Duration::from_secs(1)));
+
+        // only day 1 exceeded its part timeout.
+        let expired = tracker.expire(t0 + Duration::from_millis(1500));
+        assert_eq!(expired.len(), 1);
+        let (day, results) = &expired[0];
+        assert_eq!(*day, day!(1));
+        assert_eq!(
+            statuses(results),
+            vec![
+                (day!(1), 1, PartStatus::Finished),
+                (day!(1), 2, PartStatus::TimedOut)
+            ]
+        );
+        assert_eq!(results[1].duration_nanos, 1.5e9);
+
+        let crashed = tracker.finish(false, t0 + Duration::from_secs(2));
+        assert_eq!(crashed.len(), 1);
+        assert_eq!(
+            statuses(&crashed[0].1),
+            vec![(day!(2), 1, PartStatus::Crashed)]
+        );
+    }
+
+    #[test]
+    fn labels_failures_with_the_parts_of_the_solution() {
+        let mut tracker = DayTracker::new(Timeouts {
+            day: Some(Duration::from_secs(1)),
+            part: None,
+        });
+        let t0 = Instant::now();
+
+        // day 5 only solves part 2, day 6 crashes between its parts.
+        tracker.track(&begin(day!(5), &[2]), t0);
+        tracker.track(&begin(day!(6), &[0, 1, 2]), t0);
+        tracker.track(&start(day!(6), 0), t0);
+        tracker.track(&finished(day!(6), 0), t0);
+        tracker.track(&start(day!(6), 1), t0);
+        tracker.track(&finished(day!(6), 1), t0);
+
+        // day 7 reported all of its parts and waits for its turn to be done.
+        let t1 = t0 + Duration::from_millis(500);
+        tracker.track(&begin(day!(7), &[1, 2]), t1);
+        tracker.track(&start(day!(7), 1), t1);
+        tracker.track(&finished(day!(7), 1), t1);
+        tracker.track(&start(day!(7), 2), t1);
+        tracker.track(&finished(day!(7), 2), t1);
+        assert!(tracker
+            .expire(t1 + Duration::from_secs(2))
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+            .all(|(day, _)| *day != day!(7)));
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        let mut tracker = DayTracker::new(Timeouts::default());
+        tracker.track(&begin(day!(5), &[2]), t0);
+        tracker.track(&begin(day!(6), &[0, 1, 2]), t0);
+        tracker.track(&finished(day!(6), 0), t0);
+        tracker.track(&finished(day!(6), 1), t0);
+        tracker.track(&begin(day!(7), &[1, 2]), t0);
+        tracker.track(&finished(day!(7), 1), t0);
+        tracker.track(&finished(day!(7), 2), t0);
+
+        let results: Vec<PartResult> = tracker
+            .finish(false, t0)
+            .into_iter()
+            .flat_map(|(_, results)| results)
+            .collect();
+        assert_eq!(
+            statuses(&results),
+            vec![
+                (day!(5), 2, PartStatus::Crashed),
+                (day!(6), 0, PartStatus::Finished),
+                (day!(6), 1, PartStatus::Finished),
+                (day!(6), 2, PartStatus::Crashed),
+                (day!(7), 1, PartStatus::Finished),
+                (day!(7), 2, PartStatus::Finished),
+            ]
+        );
+    }
 
-                timings.total_nanos += nanos;
-            });
+    #[test]
+    fn finds_executable_of_build_artifact() {
+        let artifact = |name: &str| {
+            format!(
+                r#"{{"reason":"compiler-artifact","target":{{"name":"{name}","kind":["bin"]}},"executable":"/target/release/{name}","fresh":true}}"#
+            )
+        };
 
-        timings
+        assert_eq!(
+            artifact_executable(&artifact("2024_05"), "2024_05"),
+            Some(PathBuf::from("/target/release/2024_05"))
+        );
+        assert_eq!(artifact_executable(&artifact("runner"), "2024_05"), None);
+        assert_eq!(
+            artifact_executable(r#"{"reason":"build-finished","success":true}"#, "2024_05"),
+            None
+        );
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn restarts_for_unfinished_days() {
+        let timeouts = Timeouts {
+            day: Some(Duration::from_secs(1)),
+            part: None,
+        };
+        let mut passes: Vec<Vec<Day>> = vec![];
+        let mut results = vec![];
+
+        restart_until_done(
+            &[day!(1), day!(2), day!(3)],
+            |remaining, on_day| {
+                passes.push(remaining.to_vec());
+                let mut tracker = DayTracker::new(timeouts);
+                let t0 = Instant::now();
+
+                if passes.len() == 1 {
+                    // day 3 finishes, day 1 hangs and day 2 is lost when the runner is killed.
+                    tracker.track(&start(day!(1), 1), t0);
+                    tracker.track(&start(day!(3), 1), t0);
+                    tracker.track(&finished(day!(3), 1), t0);
+                    if let Tracked::Done(day, results) = tracker.track(&done(day!(3)), t0) {
+                        on_day(day, results);
+                    }
+                    for (day, results) in tracker.expire(t0 + Duration::from_secs(2)) {
+                        on_day(day, results);
+                    }
+                } else {
+                    tracker.track(&start(day!(2), 1), t0);
+                    for (day, results) in tracker.finish(false, t0) {
+                        on_day(day, results);
+                    }
+                }
+
+                Ok(ExitStatus::default())
+            },
+            |result| results.push(result),
+        )
+        .unwrap();
+
+        assert_eq!(passes, vec![vec![day!(1), day!(2), day!(3)], vec![day!(2)]]);
+        assert_eq!(
+            statuses(&results),
+            vec![
+                (day!(1), 1, PartStatus::TimedOut),
+                (day!(2), 1, PartStatus::Crashed),
+                (day!(3), 1, PartStatus::Finished),
+            ]
+        );
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
+    #[test]
+    fn records_remaining_days_without_progress_as_crashed() {
+        let mut passes = 0;
+        let mut results = vec![];
 
-        use crate::day;
+        restart_until_done(
+            &[day!(2), day!(4)],
+            |_, _| {
+                passes += 1;
+                Ok(ExitStatus::default())
+            },
+            |result| results.push(result),
+        )
+        .unwrap();
 
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        assert_eq!(passes, 1);
+        assert_eq!(
+            statuses(&results),
+            vec![
+                (day!(2), 1, PartStatus::Crashed),
+                (day!(4), 1, PartStatus::Crashed)
+            ]
+        );
     }
 }
@@ -2,65 +2,280 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
-use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::sync::Once;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{cmp, env, panic, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::parse::ParseReport;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+use crate::template::answers::{self, AnswerCheck};
+use crate::template::aoc_client::{self, SubmissionOutcome};
+use crate::template::heap::{self, AllocStats};
+use crate::template::input::InputSource;
+use crate::template::ledger::{self, Ledger, Submission};
+use crate::template::output::{
+    part_label, OutputFormat, PartResult, PartStatus, RunnerEvent, PARSE_PART,
+};
+use crate::template::registry::Solution;
+use crate::template::stats::{nanos_to_duration, BenchStats};
+use crate::template::{Error, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED};
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Prefix of the line that reports the sample distribution of a benched part.
+const STATS_PREFIX: &str = "  ↳ ";
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Read the input selected by the arguments of a solution binary: the puzzle input by default,
+/// `--input <path>` for any file, `--example [N]` for an example and `-` for stdin.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    input_source()
+        .read(puzzle)
+        .unwrap_or_else(|e| exit_with_error(&e))
+}
+
+/// Report an error of a solution binary and exit with the exit code of the error.
+pub(crate) fn exit_with_error(e: &Error) -> ! {
+    eprintln!("Error: {e}");
+    process::exit(e.exit_code())
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    let format = output_format();
+    let source = input_source();
+    let part_str = part_label(part);
+
+    print_start_event(puzzle, part);
+
+    let (result, mut part_result) = solve(func, input, puzzle, part, |result| {
+        if format == OutputFormat::Text {
+            print_result(result, &part_str, "");
+            print_benching();
+        }
+    });
+
+    // known answers only apply to the puzzle input.
+    if !source.is_puzzle_input() {
+        part_result.check = AnswerCheck::Unknown;
+    }
+
+    print_output(&part_result, format);
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, puzzle, part, &source);
+    }
+}
+
+/// Run the parse step of a solution, whose output is passed to each part. The step is timed like a part.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, puzzle: PuzzleId) -> T {
+    let format = output_format();
+
+    print_start_event(puzzle, PARSE_PART);
+
+    let (parsed, part_result) = parse(func, input, puzzle, || {
+        if format == OutputFormat::Text {
+            print!("{}:", part_label(PARSE_PART));
+            print_benching();
+        }
+    });
+
+    print_output(&part_result, format);
+
+    parsed
+}
+
+/// Run the parse step of a solution without printing its result, used by the runner binary.
+pub fn solve_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, puzzle: PuzzleId) -> PartResult {
+    parse(func, input, puzzle, || {}).1
+}
+
+fn parse<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    puzzle: PuzzleId,
+    hook: impl Fn(),
+) -> (T, PartResult) {
+    let (parsed, stats, alloc) = run_timed(func, input, |_| hook());
+
+    let part_result = PartResult {
+        day: puzzle.day,
+        part: PARSE_PART,
+        answer: None,
+        duration_nanos: stats.median_nanos,
+        samples: stats.samples,
+        stats: (stats.samples > 1).then_some(stats),
+        alloc,
+        check: AnswerCheck::Unknown,
+        status: PartStatus::Finished,
+    };
+
+    (parsed, part_result)
+}
+
+/// Announce the parts of a solution binary before its first part starts.
+pub fn print_begin_event(solution: &Solution) {
+    if has_events() {
+        println!("{}", solution.begin_event().to_json_line());
+    }
+}
+
+fn print_start_event(puzzle: PuzzleId, part: u8) {
+    if has_events() {
+        let event = RunnerEvent::Start {
+            day: puzzle.day,
+            part,
+        };
+        println!("{}", event.to_json_line());
+    }
+}
+
+/// Show that a part is being benched after its first result has been printed.
+fn print_benching() {
+    if is_timed() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+}
+
+fn print_output(part_result: &PartResult, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => print_part_result(part_result),
+        OutputFormat::Json => println!("{}", part_result.to_json_line()),
+    }
+}
+
+/// Run a solution part without printing or submitting its result, used by the runner binary.
+pub fn solve_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) -> PartResult {
+    solve(func, input, puzzle, part, |_| {}).1
+}
+
+fn solve<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    hook: impl Fn(&Option<T>),
+) -> (Option<T>, PartResult) {
+    let (result, stats, alloc) = run_timed(func, input, hook);
+
+    let answer = result.as_ref().map(ToString::to_string);
+
+    let part_result = PartResult {
+        day: puzzle.day,
+        part,
+        check: answers::check(puzzle, part, answer.as_deref()),
+        answer,
+        duration_nanos: stats.median_nanos,
+        samples: stats.samples,
+        stats: (stats.samples > 1).then_some(stats),
+        alloc,
+        status: PartStatus::Finished,
+    };
+
+    (result, part_result)
+}
+
+/// Print the final, human-readable result line of a solution part, followed by its sample distribution if benched.
+pub fn print_part_result(part_result: &PartResult) {
+    let part_str = part_label(part_result.part);
+
+    if part_result.is_failure() {
+        println!(
+            "{part_str}: {ANSI_RED}{}{ANSI_RESET}",
+            format_failure(part_result)
+        );
+        return;
+    }
+
+    let duration_str = format_duration(
+        &nanos_to_duration(part_result.duration_nanos),
+        part_result.samples,
+        part_result.alloc.as_ref(),
+    );
+
+    // the parse step has no answer to show or check.
+    if part_result.is_parse() {
+        println!("\r{part_str}:{duration_str}");
+    } else {
+        let duration_str = format!("{duration_str}{}", format_check(&part_result.check));
+        print_result(&part_result.answer, &part_str, &duration_str);
+    }
+
+    if let Some(stats) = &part_result.stats {
+        println!("{}", format_stats(stats));
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Heap usage is measured for the first execution only, see [`heap::measure`].
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, BenchStats, Option<AllocStats>) {
+    report_parse_errors();
+
     let timer = Instant::now();
-    let result = {
+    let (result, alloc) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        heap::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if is_timed() {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        BenchStats::from_single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats, alloc)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Print the [`ParseReport`] of a solution that failed to parse its input instead of a panic message and backtrace.
+/// Other panics are left to the previous hook.
+fn report_parse_errors() {
+    static INSTALL: Once = Once::new();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            match info.payload().downcast_ref::<ParseReport>() {
+                Some(report) => eprintln!("{ANSI_RED}{report}{ANSI_RESET}"),
+                None => previous(info),
+            }
+        }));
+    });
+}
 
+/// Bench a solution part. A number of warm-up iterations is executed and discarded before samples are collected.
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm up caches and branch predictors for roughly a tenth of the sampling time.
+    let warmup_iterations = (bench_iterations / 10).max(1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,26 +286,80 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Parse the input source passed to the solution binary, see [`read_input`].
+fn input_source() -> InputSource {
+    let args: Vec<String> = env::args().collect();
+
+    InputSource::from_args(&args).unwrap_or_else(|e| exit_with_error(&Error::Usage(e)))
+}
+
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+/// Whether to print progress events for `run_multi`, see [`RunnerEvent`].
+pub(crate) fn has_events() -> bool {
+    env::args().any(|x| x == "--events")
+}
+
+/// Parse the `--format` argument passed to the solution binary, defaulting to text output.
+fn output_format() -> OutputFormat {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|x| x == "--format")
+        .and_then(|ix| args.get(ix + 1))
+        .map_or(OutputFormat::Text, |format| {
+            format
+                .parse::<OutputFormat>()
+                .unwrap_or_else(|e| exit_with_error(&Error::Usage(e.to_string())))
+        })
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+fn format_duration(duration: &Duration, samples: u128, alloc: Option<&AllocStats>) -> String {
+    let alloc_str = alloc.map_or_else(String::new, |alloc| format!(", {alloc}"));
+
     if samples == 1 {
-        format!(" ({duration:.1?})")
+        format!(" ({duration:.1?}{alloc_str})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} @ {samples} samples{alloc_str})")
+    }
+}
+
+/// Formats the sample distribution of a benched part, e.g. `  ↳ min 1.0ms, p95 1.4ms, max 2.0ms, mean 1.1ms, σ 100.0µs, 2 outliers`.
+fn format_stats(stats: &BenchStats) -> String {
+    format!(
+        "{STATS_PREFIX}min {:.1?}, p95 {:.1?}, max {:.1?}, mean {:.1?}, σ {:.1?}, {} outliers",
+        nanos_to_duration(stats.min_nanos),
+        nanos_to_duration(stats.p95_nanos),
+        nanos_to_duration(stats.max_nanos),
+        nanos_to_duration(stats.mean_nanos),
+        nanos_to_duration(stats.std_dev_nanos),
+        stats.outliers
+    )
+}
+
+/// Describe a part that timed out or crashed, e.g. `timed out after 10.0s`.
+pub fn format_failure(part_result: &PartResult) -> String {
+    let duration = nanos_to_duration(part_result.duration_nanos);
+    match part_result.status {
+        PartStatus::TimedOut => format!("timed out after {duration:.1?}"),
+        PartStatus::Crashed => format!("crashed after {duration:.1?}"),
+        PartStatus::Finished => "finished".into(),
+    }
+}
+
+/// Mark a part as matching, differing from or missing a known answer.
+fn format_check(check: &AnswerCheck) -> String {
+    match check {
+        AnswerCheck::Correct => format!(" {ANSI_GREEN}✓{ANSI_RESET}"),
+        AnswerCheck::Wrong { expected } => {
+            format!(" {ANSI_RED}✗ expected {expected}{ANSI_RESET}")
+        }
+        AnswerCheck::Unknown => format!(" {ANSI_ITALIC}?{ANSI_RESET}"),
     }
 }
 
@@ -131,39 +400,86 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    source: &InputSource,
+) -> Option<Result<SubmissionOutcome, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let part_submit = args
+        .iter()
+        .position(|x| x == "--submit")
+        .and_then(|ix| args.get(ix + 1))
+        .and_then(|part| part.parse::<u8>().ok())
+        .unwrap_or_else(|| {
+            exit_with_error(&Error::Usage(
+                "unexpected command-line input. Format: cargo solve 1 --submit 1 --year 2024"
+                    .into(),
+            ))
+        });
+
+    if part_submit != part {
+        return None;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    if !source.is_puzzle_input() {
+        eprintln!(
+            "Not submitting result: the solution ran on {source} instead of the puzzle input."
+        );
+        return None;
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    let answer = result.to_string();
+    let ledger = Ledger::read_from_file();
+    let ignore_ledger = args.contains(&"--ignore-ledger".into());
 
-    if part_submit != part {
-        return None;
+    match ledger::check_submission(&ledger, ignore_ledger, puzzle, part, &answer, unix_now()) {
+        Ok(Some(warning)) => eprintln!("{ANSI_BOLD}Warning:{ANSI_RESET} {warning}"),
+        Ok(None) => {}
+        Err(refusal) => {
+            eprintln!("Not submitting result: {refusal}");
+            return None;
+        }
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    // an unreadable ledger is not stored, so that it is not overwritten.
+    let mut ledger = ledger.ok();
+
+    println!("Submitting result...");
+    let outcome = aoc_client::submit(puzzle, part, &answer);
+
+    match &outcome {
+        Ok(outcome) => {
+            println!("{outcome}");
+
+            if *outcome == SubmissionOutcome::Correct {
+                if let Err(e) = answers::store(puzzle, part, &answer) {
+                    eprintln!("failed to store answer: {e}");
+                }
+            }
+
+            if let Some(ledger) = &mut ledger {
+                ledger.record(Submission::new(puzzle, part, &answer, outcome, unix_now()));
+                if let Err(e) = ledger.store_file() {
+                    eprintln!("failed to store submission ledger: {e}");
+                }
+            }
+        }
+        Err(e) => eprintln!("failed to submit result: {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(outcome)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
 }
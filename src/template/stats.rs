@@ -0,0 +1,312 @@
+/// Summary statistics for a series of benchmark samples.
+use std::{collections::HashMap, fmt::Display, time::Duration};
+use tinyjson::JsonValue;
+
+/// Samples further than this many (scaled) median absolute deviations from the median are rejected as outliers.
+const OUTLIER_THRESHOLD: f64 = 3.0;
+
+/// Scale factor that makes the MAD a consistent estimator of the standard deviation for normally distributed data.
+const MAD_SCALE: f64 = 1.4826;
+
+/// Represents the sample distribution of a benchmarked solution part. All durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub min_nanos: f64,
+    pub median_nanos: f64,
+    pub p95_nanos: f64,
+    pub max_nanos: f64,
+    pub mean_nanos: f64,
+    pub std_dev_nanos: f64,
+    /// Number of samples taken, including rejected outliers.
+    pub samples: u128,
+    /// Number of samples rejected as outliers.
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Stats for a solution part that was executed exactly once.
+    pub fn from_single(duration: Duration) -> Self {
+        Self::from_samples(&[duration])
+    }
+
+    /// Compute stats from a series of timing samples, rejecting outliers based on their MAD.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot compute stats without samples.");
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let median = percentile(&nanos, 50.0);
+        let mad = median_absolute_deviation(&nanos, median);
+
+        let kept: Vec<f64> = if mad > 0.0 {
+            let limit = OUTLIER_THRESHOLD * MAD_SCALE * mad;
+            nanos
+                .iter()
+                .copied()
+                .filter(|x| (x - median).abs() <= limit)
+                .collect()
+        } else {
+            nanos.clone()
+        };
+
+        let mean = kept.iter().sum::<f64>() / kept.len() as f64;
+        let variance = kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / kept.len() as f64;
+
+        BenchStats {
+            min_nanos: kept[0],
+            median_nanos: percentile(&kept, 50.0),
+            p95_nanos: percentile(&kept, 95.0),
+            max_nanos: kept[kept.len() - 1],
+            mean_nanos: mean,
+            std_dev_nanos: variance.sqrt(),
+            samples: nanos.len() as u128,
+            outliers: (nanos.len() - kept.len()) as u128,
+        }
+    }
+}
+
+/// Two-sided 95% quantiles of Student's t-distribution for 1 to 30 degrees of freedom.
+const T_QUANTILES_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// How much faster a candidate is than a baseline, with a 95% confidence interval. Values above `1.0` are speedups.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Speedup {
+    pub ratio: f64,
+    pub low: f64,
+    pub high: f64,
+}
+
+impl Speedup {
+    /// Compute the speedup from paired durations, where the `i`-th baseline and candidate durations were measured in the same round.
+    /// The interval is a t-interval of the log ratios of each pair, so that it is symmetric for speedups and slowdowns.
+    ///
+    /// Returns `None` for fewer than two pairs, as a single pair has no spread to estimate an interval from.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_pairs(baseline: &[f64], candidate: &[f64]) -> Option<Self> {
+        let log_ratios: Vec<f64> = baseline
+            .iter()
+            .zip(candidate)
+            .map(|(baseline, candidate)| (baseline / candidate).ln())
+            .collect();
+
+        let n = log_ratios.len();
+        if n < 2 {
+            return None;
+        }
+
+        let mean = log_ratios.iter().sum::<f64>() / n as f64;
+        let variance = log_ratios.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        let t = T_QUANTILES_95.get(n - 2).copied().unwrap_or(1.96);
+        let margin = t * (variance / n as f64).sqrt();
+
+        Some(Speedup {
+            ratio: mean.exp(),
+            low: (mean - margin).exp(),
+            high: (mean + margin).exp(),
+        })
+    }
+
+    /// Whether the whole interval lies above `1.0`.
+    pub fn is_faster(&self) -> bool {
+        self.low > 1.0
+    }
+
+    /// Whether the whole interval lies below `1.0`.
+    pub fn is_slower(&self) -> bool {
+        self.high < 1.0
+    }
+}
+
+impl Display for Speedup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.2}x [{:.2}x, {:.2}x]",
+            self.ratio, self.low, self.high
+        )
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Median of an unsorted, non-empty slice.
+pub fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    percentile(&sorted, 50.0)
+}
+
+/// Linearly interpolated percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let rank = pct / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let lower = rank.floor() as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    let weight = rank - lower as f64;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+fn median_absolute_deviation(sorted: &[f64], median: f64) -> f64 {
+    let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+    deviations.sort_unstable_by(f64::total_cmp);
+    percentile(&deviations, 50.0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert(
+            "std_dev_nanos".into(),
+            JsonValue::Number(value.std_dev_nanos),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            min_nanos: number("min_nanos")?,
+            median_nanos: number("median_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+            max_nanos: number("max_nanos")?,
+            mean_nanos: number("mean_nanos")?,
+            std_dev_nanos: number("std_dev_nanos")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{percentile, BenchStats, Speedup};
+
+    fn from_nanos(nanos: &[u64]) -> BenchStats {
+        let samples: Vec<Duration> = nanos.iter().map(|n| Duration::from_nanos(*n)).collect();
+        BenchStats::from_samples(&samples)
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_single(Duration::from_nanos(100));
+        assert_eq!(stats.min_nanos, 100.0);
+        assert_eq!(stats.median_nanos, 100.0);
+        assert_eq!(stats.max_nanos, 100.0);
+        assert_eq!(stats.std_dev_nanos, 0.0);
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn computes_percentiles() {
+        let sorted: Vec<f64> = (1..=11).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 6.0);
+        assert_eq!(percentile(&sorted, 95.0), 10.5);
+        assert_eq!(percentile(&sorted, 100.0), 11.0);
+    }
+
+    #[test]
+    fn computes_distribution() {
+        let stats = from_nanos(&[40, 10, 30, 20, 50]);
+        assert_eq!(stats.min_nanos, 10.0);
+        assert_eq!(stats.median_nanos, 30.0);
+        assert_eq!(stats.max_nanos, 50.0);
+        assert_eq!(stats.mean_nanos, 30.0);
+        assert_eq!(stats.std_dev_nanos, 200_f64.sqrt());
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = from_nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]);
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max_nanos, 102.0);
+        assert_eq!(stats.median_nanos, 100.0);
+    }
+
+    #[test]
+    fn keeps_constant_samples() {
+        let stats = from_nanos(&[100, 100, 100, 100]);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.std_dev_nanos, 0.0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = from_nanos(&[40, 10, 30, 20, 50]);
+        let json = tinyjson::JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_without_samples() {
+        BenchStats::from_samples(&[]);
+    }
+
+    #[test]
+    fn computes_speedups() {
+        let speedup = Speedup::from_pairs(&[200.0, 210.0, 190.0], &[100.0, 100.0, 100.0]).unwrap();
+        assert!((speedup.ratio - 1.998).abs() < 1e-3);
+        assert!(speedup.low < speedup.ratio && speedup.ratio < speedup.high);
+        assert!(speedup.is_faster());
+        assert_eq!(speedup.is_slower(), false);
+
+        let slowdown = Speedup::from_pairs(&[100.0, 100.0], &[200.0, 200.0]).unwrap();
+        assert_eq!(slowdown.ratio, 0.5);
+        assert_eq!(slowdown.to_string(), "0.50x [0.50x, 0.50x]");
+        assert!(slowdown.is_slower());
+
+        assert_eq!(Speedup::from_pairs(&[], &[]), None);
+        assert_eq!(Speedup::from_pairs(&[200.0], &[100.0]), None);
+    }
+}
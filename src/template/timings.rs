@@ -1,19 +1,106 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::heap::AllocStats;
+use crate::template::output::{PartResult, PARSE_PART};
+use crate::template::stats::{nanos_to_duration, BenchStats};
+use crate::template::{Day, Error, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+#[must_use]
+pub fn get_timings_path(year: Year) -> String {
+    format!("./data/{year}/timings.json")
+}
+
+/// Version of the JSON schema timings are stored in.
+/// Version 1 stored parts as preformatted strings, e.g. `"74.13ms"`, and is migrated when read.
+pub const SCHEMA_VERSION: u8 = 2;
+
+/// Represents the benchmark time of a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Median duration in nanoseconds.
+    pub nanos: f64,
+    /// Number of samples taken, `0` if unknown (i.e. migrated from schema version 1).
+    pub samples: u128,
+    pub stats: Option<BenchStats>,
+    /// Heap usage of the part, only present if it was benched with `--alloc`.
+    pub alloc: Option<AllocStats>,
+}
+
+impl Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1?}", nanos_to_duration(self.nanos))
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// The parse step, only present for solutions that parse their input once for both parts.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Collect the timings of the successful parts of a day.
+    pub fn from_results(day: Day, results: &[PartResult]) -> Self {
+        let mut timing = Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+        };
+
+        for result in results.iter().filter(|r| r.day == day && r.is_success()) {
+            let part = Some(PartTiming {
+                nanos: result.duration_nanos,
+                samples: result.samples,
+                stats: result.stats.clone(),
+                alloc: result.alloc,
+            });
+
+            match result.part {
+                PARSE_PART => timing.parse = part,
+                1 => timing.part_1 = part,
+                2 => timing.part_2 = part,
+                _ => continue,
+            }
+
+            timing.total_nanos += result.duration_nanos;
+        }
+
+        timing
+    }
+
+    /// The timing of a part or of the parse step, if present.
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            PARSE_PART => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Median duration of a part in nanoseconds, if present.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        self.part(part).map(|p| p.nanos)
+    }
+
+    /// Parse a timing stored in the given schema version.
+    pub fn from_json(value: &JsonValue, version: u8) -> Result<Self, String> {
+        match version {
+            1 => migrate_v1(value),
+            SCHEMA_VERSION => Timing::try_from(value),
+            v => Err(format!("unsupported timings schema version {v}.")),
+        }
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,19 +109,32 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let path = get_timings_path(year);
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+        fs::File::create(&path)
+            .and_then(|mut file| json.format_to(&mut file))
+            .map_err(|e| Error::Timings {
+                path,
+                reason: e.to_string(),
+            })
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings from the JSON file of a year, migrating older schema versions.
+    /// If not present, returns empty timings. A file that can't be parsed is an error, so that it is not overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, Error> {
+        let path = get_timings_path(year);
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                Timings::try_from(contents).map_err(|reason| Error::Timings { path, reason })
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(Error::Timings {
+                path,
+                reason: e.to_string(),
+            }),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,7 +151,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -73,6 +173,10 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,9 +192,13 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = schema_version(json)?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -99,12 +207,93 @@ impl TryFrom<String> for Timings {
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|t| Timing::from_json(t, version))
                 .collect::<Result<_, _>>()?,
         })
     }
 }
 
+/// Read the schema version of a JSON document. Documents without a version predate versioning.
+pub fn schema_version(json: &HashMap<String, JsonValue>) -> Result<u8, String> {
+    match json.get("version") {
+        None => Ok(1),
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(v) => v
+            .get::<f64>()
+            .map(|v| *v as u8)
+            .ok_or("expected `json.version` to be a number.".into()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "alloc".into(),
+            value
+                .alloc
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.samples to be a number.")? as u128;
+
+        let stats = json
+            .get("stats")
+            .filter(|v| !v.is_null())
+            .map(BenchStats::try_from)
+            .transpose()?;
+
+        // timings stored without `--alloc` don't contain alloc stats.
+        let alloc = json
+            .get("alloc")
+            .filter(|v| !v.is_null())
+            .map(AllocStats::try_from)
+            .transpose()?;
+
+        Ok(PartTiming {
+            nanos,
+            samples,
+            stats,
+            alloc,
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<&Timing> for JsonValue {
@@ -113,24 +302,26 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value
+                .part_1
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
-
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value
+                .part_2
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
         JsonValue::Object(map)
@@ -145,62 +336,140 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
-
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let day = parse_day(json)?;
+
+        let part = |key: &str| {
+            json.get(key)
+                .ok_or(format!("Expected timing.{key} to be null or an object."))
+                .and_then(|v| {
+                    if v.is_null() {
+                        Ok(None)
+                    } else {
+                        PartTiming::try_from(v).map(Some)
+                    }
+                })
+        };
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+        // timings of solutions without a parse step don't contain one.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(_) => part("parse")?,
+        };
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            parse,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+            total_nanos: parse_total_nanos(json)?,
         })
     }
 }
 
+/// Migrate a timing stored in schema version 1, where parts were preformatted duration strings.
+fn migrate_v1(value: &JsonValue) -> Result<Timing, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?;
+
+    let day = parse_day(json)?;
+
+    let part = |key: &str| -> Result<Option<PartTiming>, String> {
+        let duration = json
+            .get(key)
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or(format!("Expected timing.{key} to be null or string."))?;
+
+        let Some(duration) = duration else {
+            return Ok(None);
+        };
+
+        let nanos = parse_duration(duration).ok_or(format!("Could not parse timing.{key}."))?;
+
+        Ok(Some(PartTiming {
+            nanos,
+            samples: 0,
+            stats: None,
+            alloc: None,
+        }))
+    };
+
+    Ok(Timing {
+        day,
+        parse: None,
+        part_1: part("part_1")?,
+        part_2: part("part_2")?,
+        total_nanos: parse_total_nanos(json)?,
+    })
+}
+
+fn parse_day(json: &HashMap<String, JsonValue>) -> Result<Day, String> {
+    json.get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.".into())
+}
+
+fn parse_total_nanos(json: &HashMap<String, JsonValue>) -> Result<f64, String> {
+    json.get("total_nanos")
+        .and_then(|v| v.get::<f64>().copied())
+        .ok_or("Expected timing.total_nanos to be a number.".into())
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parse a formatted duration back to nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
-mod tests {
+pub(crate) mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    /// A part timed at `nanos` over 10 samples, shared by the tests of modules that consume timings.
+    pub(crate) fn part(nanos: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos,
+            samples: 10,
+            stats: None,
+            alloc: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: part(10e6),
+                    part_2: part(20e6),
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: part(30e6),
+                    part_2: part(40e6),
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: part(40e6),
                     part_2: None,
                     total_nanos: 4e+10,
                 },
@@ -213,16 +482,34 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 10, "stats": null }, "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_nanos(1), Some(1_000_000_f64));
+            assert_eq!(timing.part_1.as_ref().unwrap().samples, 10);
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn migrates_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": "74.13µs", "total_nanos": 1574130 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_nanos(1), Some(1_500_000_f64));
+            assert!((timing.part_nanos(2).unwrap() - 74_130_f64).abs() < 1e-6);
+            assert_eq!(timing.part_1.as_ref().unwrap().samples, 0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 99, "data": [{ "day": "01", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -250,6 +537,26 @@ mod tests {
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = crate::template::timings::Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[1].part_1, timings.data[1].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+        }
+
+        #[test]
+        fn roundtrips_parse_step() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = super::part(5e6);
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = crate::template::timings::Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parse, timings.data[0].parse);
+            assert_eq!(parsed.data[1].parse, None);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -274,18 +581,21 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::part;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: part(1e6),
+                    part_2: part(2e6),
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +603,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: part(1e6),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +618,79 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod from_results {
+        use crate::{
+            day,
+            template::{
+                answers::AnswerCheck,
+                output::{PartResult, PartStatus},
+                timings::Timing,
+            },
+        };
+
+        fn result(part: u8, answer: Option<&str>, duration_nanos: f64) -> PartResult {
+            PartResult {
+                day: day!(1),
+                part,
+                answer: answer.map(Into::into),
+                duration_nanos,
+                samples: 10,
+                stats: None,
+                alloc: None,
+                check: AnswerCheck::Unknown,
+                status: PartStatus::Finished,
+            }
+        }
+
+        #[test]
+        fn collects_part_timings() {
+            let timing = Timing::from_results(
+                day!(1),
+                &[
+                    result(1, Some("0"), 74.0),
+                    result(2, Some("10"), 74_130_000.0),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 74_130_074.0);
+            assert_eq!(timing.part_nanos(1), Some(74.0));
+            assert_eq!(timing.part_2.as_ref().unwrap().samples, 10);
+            assert_eq!(timing.part_2.unwrap().to_string(), "74.1ms");
+        }
+
+        #[test]
+        fn collects_parse_step() {
+            let timing = Timing::from_results(
+                day!(1),
+                &[
+                    result(0, None, 1_000.0),
+                    result(1, Some("0"), 74.0),
+                    result(2, Some("10"), 26.0),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 1_100.0);
+            assert_eq!(timing.part_nanos(0), Some(1_000.0));
+        }
+
+        #[test]
+        fn skips_failed_parts() {
+            let timing = Timing::from_results(
+                day!(1),
+                &[result(1, None, 100.0), result(2, Some("10"), 2e9)],
+            );
+            assert_eq!(timing.total_nanos, 2e9);
+            assert_eq!(timing.part_1.is_none(), true);
+            assert_eq!(timing.part_2.unwrap().to_string(), "2.0s");
         }
     }
 
@@ -331,6 +708,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
@@ -351,6 +729,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
@@ -0,0 +1,518 @@
+/// The 3-bit computer of 2024 day 17: a virtual machine with single-step tracing, breakpoints and output hooks, a disassembler,
+/// and a reverse search for the value of register A that makes a program output itself.
+use std::{collections::BTreeSet, fmt::Display, ops::ControlFlow};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Adv = 0,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Opcode {
+    const ALL: [Opcode; 8] = [
+        Opcode::Adv,
+        Opcode::Bxl,
+        Opcode::Bst,
+        Opcode::Jnz,
+        Opcode::Bxc,
+        Opcode::Out,
+        Opcode::Bdv,
+        Opcode::Cdv,
+    ];
+
+    /// Whether the operand is a combo operand, i.e. `0..=3` or one of the registers, instead of a literal.
+    pub fn has_combo_operand(self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+}
+
+impl TryFrom<u8> for Opcode {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::ALL.get(usize::from(value)).copied().ok_or(value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Registers {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+impl Display for Registers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "A={} B={} C={}", self.a, self.b, self.c)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VmError {
+    /// The opcode at `ip` is not one of the eight instructions.
+    InvalidOpcode { ip: usize, opcode: u8 },
+    /// The instruction at `ip` uses the reserved combo operand `7`.
+    ReservedOperand { ip: usize },
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::InvalidOpcode { ip, opcode } => write!(f, "invalid opcode {opcode} at {ip}"),
+            VmError::ReservedOperand { ip } => write!(f, "reserved combo operand 7 at {ip}"),
+        }
+    }
+}
+
+impl std::error::Error for VmError {}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: u8,
+}
+
+impl Instruction {
+    /// Decode the instruction at `ip`, or `None` if the program halts there.
+    pub fn decode(program: &[u8], ip: usize) -> Result<Option<Self>, VmError> {
+        let (Some(&opcode), Some(&operand)) = (program.get(ip), program.get(ip + 1)) else {
+            return Ok(None);
+        };
+
+        let opcode =
+            Opcode::try_from(opcode).map_err(|opcode| VmError::InvalidOpcode { ip, opcode })?;
+        Ok(Some(Instruction { opcode, operand }))
+    }
+
+    /// The effect of the instruction as pseudo code, e.g. `c = a >> b` for `cdv b`.
+    pub fn pseudo_code(&self) -> String {
+        let combo = self.combo_name();
+        let literal = self.operand;
+
+        match self.opcode {
+            Opcode::Adv => format!("a = a >> {combo}"),
+            Opcode::Bxl => format!("b = b ^ {literal}"),
+            Opcode::Bst => format!("b = {combo} % 8"),
+            Opcode::Jnz => format!("if a != 0: jump {literal}"),
+            Opcode::Bxc => "b = b ^ c".into(),
+            Opcode::Out => format!("out {combo} % 8"),
+            Opcode::Bdv => format!("b = a >> {combo}"),
+            Opcode::Cdv => format!("c = a >> {combo}"),
+        }
+    }
+
+    fn combo_name(&self) -> String {
+        match self.operand {
+            0..=3 => self.operand.to_string(),
+            4 => "a".into(),
+            5 => "b".into(),
+            6 => "c".into(),
+            _ => "?".into(),
+        }
+    }
+}
+
+/// Formats the instruction as assembly, e.g. `adv 3` or `out b`.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.opcode {
+            Opcode::Bxc => write!(f, "{}", self.opcode.mnemonic()),
+            opcode if opcode.has_combo_operand() => {
+                write!(f, "{} {}", opcode.mnemonic(), self.combo_name())
+            }
+            opcode => write!(f, "{} {}", opcode.mnemonic(), self.operand),
+        }
+    }
+}
+
+/// List the instructions of a program with their address and pseudo code, one per line.
+pub fn disassemble(program: &[u8]) -> Result<String, VmError> {
+    let mut lines = vec![];
+    for ip in (0..program.len()).step_by(2) {
+        let Some(instruction) = Instruction::decode(program, ip)? else {
+            break;
+        };
+        lines.push(format!(
+            "{ip:>3}: {:<8}{}",
+            instruction.to_string(),
+            instruction.pseudo_code()
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A single executed instruction, see [`Vm::step`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub ip: usize,
+    pub instruction: Instruction,
+    /// The registers after the instruction.
+    pub registers: Registers,
+    pub output: Option<u8>,
+}
+
+/// Formats the trace like a debugger, e.g. ` 12: out b   A=8 B=5 C=3 → 5`.
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>3}: {:<8}{}",
+            self.ip,
+            self.instruction.to_string(),
+            self.registers
+        )?;
+        if let Some(value) = self.output {
+            write!(f, " → {value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Why [`Vm::run`] returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// The instruction pointer left the program.
+    Halted,
+    /// The machine is about to execute the instruction at a breakpoint.
+    Breakpoint(usize),
+    /// The output hook returned [`ControlFlow::Break`].
+    Hook,
+}
+
+type OutputHook<'a> = Box<dyn FnMut(u8) -> ControlFlow<()> + 'a>;
+
+pub struct Vm<'a> {
+    program: &'a [u8],
+    pub registers: Registers,
+    pub ip: usize,
+    output: Vec<u8>,
+    breakpoints: BTreeSet<usize>,
+    output_hook: Option<OutputHook<'a>>,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(program: &'a [u8], registers: Registers) -> Self {
+        Vm {
+            program,
+            registers,
+            ip: 0,
+            output: vec![],
+            breakpoints: BTreeSet::new(),
+            output_hook: None,
+        }
+    }
+
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    pub fn into_output(self) -> Vec<u8> {
+        self.output
+    }
+
+    pub fn add_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.insert(ip);
+    }
+
+    pub fn remove_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.remove(&ip);
+    }
+
+    /// Call `hook` with each output value during [`Vm::run`]. Returning [`ControlFlow::Break`] stops the machine.
+    pub fn on_output(&mut self, hook: impl FnMut(u8) -> ControlFlow<()> + 'a) {
+        self.output_hook = Some(Box::new(hook));
+    }
+
+    /// Execute a single instruction, or return `None` if the machine halted.
+    pub fn step(&mut self) -> Result<Option<Trace>, VmError> {
+        let ip = self.ip;
+        let Some(instruction) = Instruction::decode(self.program, ip)? else {
+            return Ok(None);
+        };
+        let operand = instruction.operand;
+        self.ip += 2;
+
+        let mut output = None;
+        match instruction.opcode {
+            Opcode::Adv => self.registers.a = self.shift_a(operand, ip)?,
+            Opcode::Bxl => self.registers.b ^= u64::from(operand),
+            Opcode::Bst => self.registers.b = self.combo(operand, ip)? % 8,
+            Opcode::Jnz => {
+                if self.registers.a != 0 {
+                    self.ip = usize::from(operand);
+                }
+            }
+            Opcode::Bxc => self.registers.b ^= self.registers.c,
+            Opcode::Out => {
+                #[allow(clippy::cast_possible_truncation)]
+                let value = (self.combo(operand, ip)? % 8) as u8;
+                self.output.push(value);
+                output = Some(value);
+            }
+            Opcode::Bdv => self.registers.b = self.shift_a(operand, ip)?,
+            Opcode::Cdv => self.registers.c = self.shift_a(operand, ip)?,
+        }
+
+        Ok(Some(Trace {
+            ip,
+            instruction,
+            registers: self.registers,
+            output,
+        }))
+    }
+
+    /// Run until the machine halts, reaches a breakpoint or the output hook stops it.
+    /// A breakpoint at the current instruction is skipped, so that calling `run` again resumes the machine.
+    pub fn run(&mut self) -> Result<Stop, VmError> {
+        let mut is_first = true;
+
+        loop {
+            if !is_first && self.breakpoints.contains(&self.ip) {
+                return Ok(Stop::Breakpoint(self.ip));
+            }
+            is_first = false;
+
+            let Some(trace) = self.step()? else {
+                return Ok(Stop::Halted);
+            };
+
+            if let (Some(value), Some(hook)) = (trace.output, self.output_hook.as_mut()) {
+                if hook(value).is_break() {
+                    return Ok(Stop::Hook);
+                }
+            }
+        }
+    }
+
+    fn combo(&self, operand: u8, ip: usize) -> Result<u64, VmError> {
+        match operand {
+            0..=3 => Ok(u64::from(operand)),
+            4 => Ok(self.registers.a),
+            5 => Ok(self.registers.b),
+            6 => Ok(self.registers.c),
+            _ => Err(VmError::ReservedOperand { ip }),
+        }
+    }
+
+    /// Divide register A by `2^combo`, which is a right shift.
+    fn shift_a(&self, operand: u8, ip: usize) -> Result<u64, VmError> {
+        let shift = self.combo(operand, ip)?;
+        Ok(u32::try_from(shift)
+            .ok()
+            .and_then(|shift| self.registers.a.checked_shr(shift))
+            .unwrap_or(0))
+    }
+}
+
+/// Run a program until it halts and return its output.
+pub fn run(program: &[u8], registers: Registers) -> Result<Vec<u8>, VmError> {
+    let mut vm = Vm::new(program, registers);
+    vm.run()?;
+    Ok(vm.into_output())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Find the smallest value of register A for which the program outputs itself.
+///
+/// Programs loop until A is zero and drop its lowest bits with an `adv` of a literal operand in each iteration,
+/// so the values of A that produce the last `n` outputs are extended bit group by bit group, starting at the last output.
+/// Returns `None` if the program has no such `adv` or no value of A works.
+pub fn find_quine(program: &[u8], registers: Registers) -> Result<Option<u64>, VmError> {
+    let mut shift = None;
+    for ip in (0..program.len()).step_by(2) {
+        match Instruction::decode(program, ip)? {
+            Some(Instruction {
+                opcode: Opcode::Adv,
+                operand: operand @ 1..=3,
+            }) => shift = Some(operand),
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    match shift {
+        Some(shift) => extend_quine(program, registers, shift, 0, program.len()),
+        None => Ok(None),
+    }
+}
+
+/// Extend `prefix`, which outputs `program[len..]`, by one bit group so that it outputs `program[len - 1..]`.
+fn extend_quine(
+    program: &[u8],
+    registers: Registers,
+    shift: u8,
+    prefix: u64,
+    len: usize,
+) -> Result<Option<u64>, VmError> {
+    let Some(start) = len.checked_sub(1) else {
+        return Ok(Some(prefix));
+    };
+    let expected = &program[start..];
+
+    for bits in 0..1 << shift {
+        let a = prefix << shift | bits;
+
+        let mut vm = Vm::new(program, Registers { a, ..registers });
+        let mut remaining = expected.iter();
+        // stop at the first deviation, as a wrong value of A might loop for long.
+        vm.on_output(move |value| {
+            if remaining.next() == Some(&value) {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+
+        if vm.run()? == Stop::Halted && vm.output() == expected {
+            if let Some(quine) = extend_quine(program, registers, shift, a, start)? {
+                return Ok(Some(quine));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use super::{disassemble, find_quine, run, Registers, Stop, Vm, VmError};
+
+    fn registers(a: u64, b: u64, c: u64) -> Registers {
+        Registers { a, b, c }
+    }
+
+    #[test]
+    fn executes_instructions() {
+        let mut vm = Vm::new(&[2, 6], registers(0, 0, 9));
+        vm.run().unwrap();
+        assert_eq!(vm.registers.b, 1);
+
+        assert_eq!(
+            run(&[5, 0, 5, 1, 5, 4], registers(10, 0, 0)).unwrap(),
+            vec![0, 1, 2]
+        );
+
+        let mut vm = Vm::new(&[0, 1, 5, 4, 3, 0], registers(2024, 0, 0));
+        vm.run().unwrap();
+        assert_eq!(vm.output(), [4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        assert_eq!(vm.registers.a, 0);
+
+        let mut vm = Vm::new(&[1, 7], registers(0, 29, 0));
+        vm.run().unwrap();
+        assert_eq!(vm.registers.b, 26);
+
+        let mut vm = Vm::new(&[4, 0], registers(0, 2024, 43690));
+        vm.run().unwrap();
+        assert_eq!(vm.registers.b, 44354);
+    }
+
+    #[test]
+    fn reports_invalid_programs() {
+        assert_eq!(
+            run(&[5, 7], Registers::default()),
+            Err(VmError::ReservedOperand { ip: 0 })
+        );
+        assert_eq!(
+            run(&[1, 0, 8, 0], Registers::default()),
+            Err(VmError::InvalidOpcode { ip: 2, opcode: 8 })
+        );
+    }
+
+    #[test]
+    fn disassembles_programs() {
+        assert_eq!(
+            disassemble(&[0, 3, 5, 4, 3, 0]).unwrap(),
+            [
+                "  0: adv 3   a = a >> 3",
+                "  2: out a   out a % 8",
+                "  4: jnz 0   if a != 0: jump 0",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn steps_and_stops_at_breakpoints() {
+        let program = [0, 1, 5, 4, 3, 0];
+        let mut vm = Vm::new(&program, registers(2024, 0, 0));
+
+        let trace = vm.step().unwrap().unwrap();
+        assert_eq!(trace.registers.a, 1012);
+        assert_eq!(trace.to_string(), "  0: adv 1   A=1012 B=0 C=0");
+        let trace = vm.step().unwrap().unwrap();
+        assert_eq!(trace.output, Some(4));
+        assert!(trace.to_string().ends_with(" → 4"));
+
+        vm.add_breakpoint(2);
+        assert_eq!(vm.run(), Ok(Stop::Breakpoint(2)));
+        assert_eq!(vm.output(), [4]);
+        assert_eq!(vm.run(), Ok(Stop::Breakpoint(2)));
+        assert_eq!(vm.output(), [4, 2]);
+
+        vm.remove_breakpoint(2);
+        assert_eq!(vm.run(), Ok(Stop::Halted));
+        assert_eq!(vm.step(), Ok(None));
+    }
+
+    #[test]
+    fn stops_from_output_hook() {
+        let program = [0, 1, 5, 4, 3, 0];
+        let mut count = 0;
+        let mut vm = Vm::new(&program, registers(2024, 0, 0));
+        vm.on_output(|_| {
+            count += 1;
+            if count == 3 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        assert_eq!(vm.run(), Ok(Stop::Hook));
+        assert_eq!(vm.output(), [4, 2, 5]);
+    }
+
+    #[test]
+    fn finds_quines() {
+        let program = [0, 3, 5, 4, 3, 0];
+        let a = find_quine(&program, registers(2024, 0, 0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(a, 117_440);
+        assert_eq!(run(&program, registers(a, 0, 0)).unwrap(), program);
+
+        assert_eq!(find_quine(&[5, 4, 3, 0], Registers::default()), Ok(None));
+    }
+}